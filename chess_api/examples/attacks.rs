@@ -0,0 +1,30 @@
+use std::time::Instant;
+
+use chess_api::board::Board;
+
+/// # Counts attack computations during perft with and without the attack cache
+///
+/// run with `cargo run --release --example attacks --features attack-stats -- <depth>`,
+/// without the cache every attack query computes attacks, so query count is the uncached cost
+fn main() {
+    let depth = std::env::args().nth(1).and_then(|depth| depth.parse().ok()).unwrap_or(4);
+
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"
+    ] {
+        let board = Board::from_fen(fen).unwrap();
+
+        Board::reset_attack_stats();
+
+        let start = Instant::now();
+        let nodes = board.perft(depth);
+        let elapsed = start.elapsed();
+        let stats = Board::attack_stats();
+
+        println!("{}", fen);
+        println!("  perft({}) = {} in {:.3}s", depth, nodes, elapsed.as_secs_f64());
+        println!("  attack computations without cache: {}", stats.queries);
+        println!("  attack computations with cache:    {} ({:.1}x fewer)", stats.computations, stats.queries as f64 / stats.computations as f64);
+    }
+}
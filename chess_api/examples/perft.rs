@@ -0,0 +1,69 @@
+use std::time::{Duration, Instant};
+
+use chess_api::board::Board;
+use chess_api::movement::{Move, Square};
+use chess_api::piece::PieceType;
+
+/// # Compares two move generators on the same board
+///
+/// table generator is `Board::perft`, which takes destinations from precomputed target tables,
+/// scan generator tries all 64 destinations of every piece with `Board::is_legal`,
+/// both run on the same bitboard-backed board, so this compares generators, not board representations
+fn main() {
+    let depth = std::env::args().nth(1).and_then(|depth| depth.parse().ok()).unwrap_or(4);
+    let board = Board::new();
+
+    for depth in 1..=depth {
+        let (nodes, table_time) = timed(|| board.perft(depth));
+        let (scan_nodes, scan_time) = timed(|| perft_scan(&mut board.clone(), depth));
+
+        assert_eq!(nodes, scan_nodes);
+
+        println!(
+            "perft({}) = {}: table generator {:.3}s, scan generator {:.3}s",
+            depth, nodes, table_time.as_secs_f64(), scan_time.as_secs_f64()
+        );
+    }
+}
+
+fn timed(f: impl FnOnce() -> u64) -> (u64, Duration) {
+    let start = Instant::now();
+    let nodes = f();
+
+    (nodes, start.elapsed())
+}
+
+fn perft_scan(board: &mut Board, depth: u8) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let mut nodes = 0;
+
+    for m in scan_moves(board) {
+        board.apply_move(m).unwrap();
+        nodes += perft_scan(board, depth - 1);
+        board.undo();
+    }
+
+    nodes
+}
+
+/// # Returns legal moves found by testing all 64 destinations of every piece of side to move
+fn scan_moves(board: &Board) -> Vec<Move> {
+    let mut moves = Vec::new();
+
+    for (start, piece) in board.pieces(Some(board.turn())) {
+        for end in (0..64).map(|index| Square::from_index(index).unwrap()) {
+            let promotes = piece.piece_type() == PieceType::Pawn && (end.y() == 0 || end.y() == 7);
+
+            let candidates = if promotes {
+                [PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight].map(|promotion| Move::with_promotion(start, end, promotion)).to_vec()
+            } else { vec![Move::new(start, end)] };
+
+            moves.extend(candidates.into_iter().filter(|m| board.is_legal(*m)));
+        }
+    }
+
+    moves
+}
@@ -0,0 +1,154 @@
+use crate::piece::{PieceColor, PieceType};
+
+
+/// # Returns bitboard with only given square index set
+pub(crate) const fn bit(index: usize) -> u64 {
+    1 << index
+}
+
+/// # Returns iterator over indices of set squares, in ascending order
+pub(crate) fn indices(mut bitboard: u64) -> impl Iterator<Item = usize> {
+    core::iter::from_fn(move || {
+        if bitboard == 0 {
+            return None;
+        }
+
+        let index = bitboard.trailing_zeros() as usize;
+        bitboard &= bitboard - 1;
+        Some(index)
+    })
+}
+
+/// # Returns index of bitboard holding pieces of given type and color
+pub(crate) fn piece_index(piece_type: PieceType, color: PieceColor) -> usize {
+    color as usize * 6 + piece_type as usize
+}
+
+/// # Returns every square piece could reach from given square on empty board
+///
+/// superset of possible destinations, board still has to validate each of them,
+/// castling is not included
+pub(crate) fn targets(piece_type: PieceType, color: PieceColor, index: usize) -> u64 {
+    match piece_type {
+        PieceType::Knight => KNIGHT_TARGETS[index],
+        PieceType::King => KING_TARGETS[index],
+        PieceType::Rook => ROOK_TARGETS[index],
+        PieceType::Bishop => BISHOP_TARGETS[index],
+        PieceType::Queen => ROOK_TARGETS[index] | BISHOP_TARGETS[index],
+        PieceType::Pawn => PAWN_TARGETS[color as usize][index]
+    }
+}
+
+/// # Returns squares attacked by pawn of given color standing on given square
+pub(crate) fn pawn_attacks(color: PieceColor, index: usize) -> u64 {
+    PAWN_ATTACKS[color as usize][index]
+}
+
+/// # Returns squares strictly between two squares on the same line, or empty bitboard
+pub(crate) fn between(start: usize, end: usize) -> u64 {
+    BETWEEN[start][end]
+}
+
+static KNIGHT_TARGETS: [u64; 64] = jump_targets(&[(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)]);
+static KING_TARGETS: [u64; 64] = jump_targets(&[(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)]);
+static ROOK_TARGETS: [u64; 64] = ray_targets(&[(1, 0), (0, 1), (-1, 0), (0, -1)]);
+static BISHOP_TARGETS: [u64; 64] = ray_targets(&[(1, 1), (-1, 1), (-1, -1), (1, -1)]);
+static PAWN_TARGETS: [[u64; 64]; 2] = [pawn_targets(1), pawn_targets(-1)];
+static PAWN_ATTACKS: [[u64; 64]; 2] = [jump_targets(&[(-1, 1), (1, 1)]), jump_targets(&[(-1, -1), (1, -1)])];
+static BETWEEN: [[u64; 64]; 64] = between_table();
+
+/// # Returns bitboard with square set if coordinates are on board
+const fn square(x: i8, y: i8) -> u64 {
+    if x >= 0 && x < 8 && y >= 0 && y < 8 {
+        bit((x + 8 * y) as usize)
+    } else { 0 }
+}
+
+const fn jump_targets(offsets: &[(i8, i8)]) -> [u64; 64] {
+    let mut table = [0; 64];
+    let mut index = 0;
+
+    while index < 64 {
+        let (x, y) = ((index % 8) as i8, (index / 8) as i8);
+        let mut i = 0;
+
+        while i < offsets.len() {
+            table[index] |= square(x + offsets[i].0, y + offsets[i].1);
+            i += 1;
+        }
+
+        index += 1;
+    }
+
+    table
+}
+
+const fn ray_targets(directions: &[(i8, i8)]) -> [u64; 64] {
+    let mut table = [0; 64];
+    let mut index = 0;
+
+    while index < 64 {
+        let mut i = 0;
+
+        while i < directions.len() {
+            let (dx, dy) = directions[i];
+            let (mut x, mut y) = ((index % 8) as i8 + dx, (index / 8) as i8 + dy);
+
+            while square(x, y) != 0 {
+                table[index] |= square(x, y);
+                x += dx;
+                y += dy;
+            }
+
+            i += 1;
+        }
+
+        index += 1;
+    }
+
+    table
+}
+
+/// # Single and double pushes and both captures, `forward` is 1 for white and -1 for black
+const fn pawn_targets(forward: i8) -> [u64; 64] {
+    let mut table = [0; 64];
+    let mut index = 0;
+
+    while index < 64 {
+        let (x, y) = ((index % 8) as i8, (index / 8) as i8);
+
+        table[index] = square(x, y + forward) | square(x, y + 2 * forward) | square(x - 1, y + forward) | square(x + 1, y + forward);
+        index += 1;
+    }
+
+    table
+}
+
+const fn between_table() -> [[u64; 64]; 64] {
+    let mut table = [[0; 64]; 64];
+    let mut start = 0;
+
+    while start < 64 {
+        let mut direction = 0;
+        let directions = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+
+        while direction < directions.len() {
+            let (dx, dy) = directions[direction];
+            let (mut x, mut y) = ((start % 8) as i8 + dx, (start / 8) as i8 + dy);
+            let mut passed = 0;
+
+            while square(x, y) != 0 {
+                table[start][(x + 8 * y) as usize] = passed;
+                passed |= square(x, y);
+                x += dx;
+                y += dy;
+            }
+
+            direction += 1;
+        }
+
+        start += 1;
+    }
+
+    table
+}
@@ -1,10 +1,24 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::Cell;
+#[cfg(feature = "attack-stats")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
 use super::piece::*;
 use super::movement::*;
-
+use super::zobrist;
+use super::bitboard;
 
 #[derive(Debug, PartialEq)]
 pub enum MoveFailReason {
-    NoSourcePiece, FriendlyFire, IllegalMove, KingAttacked
+    NoSourcePiece, FriendlyFire, IllegalMove, KingAttacked, IllegalPromotion, WrongTurn
+}
+
+/// # User facing reasons why move is not legal, see `Board::explain_illegal`
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum IllegalReason {
+    NoPieceAtStart, WrongTurn, CapturingOwnPiece, PieceCannotMoveThatWay, PathBlocked, LeavesKingInCheck, InvalidPromotion
 }
 
 #[derive(Debug, PartialEq)]
@@ -12,8 +26,200 @@ pub enum PostMoveState {
     Normal, Check, Checkmate, Stelemate
 }
 
+/// # State of the game in current position
+///
+/// `Check` holds color of the side in check, `Checkmate` holds color of the winner
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum GameState {
+    Ongoing, Check(PieceColor), Checkmate(PieceColor), Stalemate, DrawByRepetition, DrawByFiftyMove, DrawByInsufficientMaterial
+}
+
+/// # Terminal result of the game, see `Board::outcome`
+///
+/// `Checkmate` holds color of the winner
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Outcome {
+    Checkmate(PieceColor), Stalemate, DrawByRepetition, DrawByFiftyMove, DrawByInsufficientMaterial
+}
+
+/// # Attack queries and attack computations counted with `attack-stats` feature
+#[cfg(feature = "attack-stats")]
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct AttackStats {
+    pub queries: u64,
+    pub computations: u64
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SanError {
+    InvalidFormat, IllegalMove, AmbiguousMove
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LanError {
+    InvalidFormat, IllegalMove
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FenError {
+    WrongFieldCount(usize), WrongRankCount(usize), WrongFileCount(usize), InvalidPiece(char), InvalidActiveColor, InvalidCastling(char),
+    InvalidEnPassant, InvalidHalfmoveClock, InvalidFullmoveNumber
+}
+
+/// # Reasons why position set up with `BoardBuilder` could not be built
+///
+/// `InvalidCastling` holds color and side ( true for kingside ) of castling right
+/// not backed by unmoved king and rook
+#[derive(Debug, PartialEq)]
+pub enum BoardError {
+    MissingKing(PieceColor), TooManyKings(PieceColor), InvalidCastling(PieceColor, bool), InvalidEnPassant(Square)
+}
+
+/// # Problems found in position by `Board::validate`
+#[derive(Debug, PartialEq)]
+pub enum PositionError {
+    MissingKing(PieceColor), TooManyKings(PieceColor), PawnOnBackRank(Square), OpponentInCheck, InvalidEnPassant(Square)
+}
+
+/// # Castling rights of both players, see `Board::castling_rights`
+///
+/// set of four independent rights which can be combined with `|`
+///
+/// ```
+/// # use chess_api::board::CastlingRights;
+/// # use chess_api::piece::PieceColor;
+///
+/// let rights = CastlingRights::WHITE_KINGSIDE | CastlingRights::BLACK_QUEENSIDE;
+///
+/// assert_eq!(rights.has(PieceColor::WHITE, true), true);
+/// assert_eq!(rights.has(PieceColor::WHITE, false), false);
+/// assert_eq!(CastlingRights::ALL.contains(rights), true);
+/// ```
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct CastlingRights(u8);
+
+impl CastlingRights {
+    pub const NONE: CastlingRights = CastlingRights(0);
+    pub const WHITE_KINGSIDE: CastlingRights = CastlingRights(1);
+    pub const WHITE_QUEENSIDE: CastlingRights = CastlingRights(2);
+    pub const BLACK_KINGSIDE: CastlingRights = CastlingRights(4);
+    pub const BLACK_QUEENSIDE: CastlingRights = CastlingRights(8);
+    pub const ALL: CastlingRights = CastlingRights(15);
+
+    /// # Returns right of given color to castle on given side ( true for kingside )
+    pub fn new(color: PieceColor, kingside: bool) -> CastlingRights {
+        CastlingRights(1 << (2 * color as u8 + !kingside as u8))
+    }
+
+    /// # Returns true if given color may castle on given side
+    pub fn has(&self, color: PieceColor, kingside: bool) -> bool {
+        self.contains(CastlingRights::new(color, kingside))
+    }
+
+    /// # Returns true if every right in other is also present in self
+    pub fn contains(&self, other: CastlingRights) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl core::ops::BitOr for CastlingRights {
+    type Output = CastlingRights;
+
+    fn bitor(self, other: CastlingRights) -> CastlingRights {
+        CastlingRights(self.0 | other.0)
+    }
+}
+
+/// # Weights used by `Board::evaluate_with`, in centipawns
+///
+/// piece values are indexed by `piece_type as usize`, structure terms are added for every
+/// occurrence so penalties are given as negative numbers, default has standard piece values
+/// and no structure terms
+#[derive(Debug, PartialEq, Clone)]
+pub struct EvalParams {
+    pub piece_values: [i32; 6],
+    pub bishop_pair: i32,
+    pub doubled_pawn: i32,
+    pub isolated_pawn: i32
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        let mut piece_values = [0; 6];
+
+        for piece_type in PieceType::ALL {
+            piece_values[piece_type as usize] = piece_type.value();
+        }
+
+        EvalParams { piece_values, bishop_pair: 0, doubled_pawn: 0, isolated_pawn: 0 }
+    }
+}
+
+/// # Start and end squares of king and rook taking part in castling
+#[derive(Clone, Copy)]
+struct Castling {
+    king: (Square, Square),
+    rook: (Square, Square)
+}
+
+/// # State needed to take back a move
+#[derive(Clone)]
+struct HistoryEntry {
+    m: Move,
+    piece: Piece,
+    captured: Option<(Square, Piece)>,
+    castling: Option<Castling>,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    attacks: [Option<u64>; 2],
+    position: u64
+}
+
+/// # Chess board
+///
+/// holds pieces, side to move and move history,
+/// cloned board is fully independent from the original
+#[derive(Clone)]
 pub struct Board {
-    pieces: [Option<Piece>; 64]
+    pieces: [Option<Piece>; 64],
+    bitboards: [u64; 12],
+    attacks: [Cell<Option<u64>>; 2],
+    turn: PieceColor,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    history: Vec<HistoryEntry>,
+    null_moves: Vec<Option<Square>>,
+    chess960: bool
+}
+
+/// # Position equality
+///
+/// boards are equal when they have the same pieces on the same squares, the same side to move,
+/// castling availability and en passant square, move history and moved flags of pieces
+/// not affecting castling are ignored
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        self.turn == other.turn
+            && self.en_passant == other.en_passant
+            && self.castling_availability() == other.castling_availability()
+            && self.pieces.iter().zip(other.pieces.iter()).all(|(a, b)| match (a, b) {
+                (Some(a), Some(b)) => a.piece_type() == b.piece_type() && a.color() == b.color(),
+                (None, None) => true,
+                _ => false
+            })
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
+    }
 }
 
 impl Board {
@@ -32,10 +238,17 @@ impl Board {
     /// }
     /// ```
     pub fn new_clear() -> Board {
-        const INIT: Option<Piece> = None;
-
         Board {
-            pieces: [INIT; 64]
+            pieces: [None; 64],
+            bitboards: [0; 12],
+            attacks: [Cell::new(None), Cell::new(None)],
+            turn: PieceColor::WHITE,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            history: Vec::new(),
+            null_moves: Vec::new(),
+            chess960: false
         }
     
     }
@@ -56,287 +269,2228 @@ impl Board {
     pub fn new() -> Board {
         let mut board = Board::new_clear();
 
-        board.pieces[Square::new(0, 0).to_index()] = Some(Piece::new(PieceType::Rook,   PieceColor::WHITE));
-        board.pieces[Square::new(1, 0).to_index()] = Some(Piece::new(PieceType::Knight, PieceColor::WHITE));
-        board.pieces[Square::new(2, 0).to_index()] = Some(Piece::new(PieceType::Bishop, PieceColor::WHITE));
-        board.pieces[Square::new(3, 0).to_index()] = Some(Piece::new(PieceType::Queen,  PieceColor::WHITE));
-        board.pieces[Square::new(4, 0).to_index()] = Some(Piece::new(PieceType::King,   PieceColor::WHITE));
-        board.pieces[Square::new(5, 0).to_index()] = Some(Piece::new(PieceType::Bishop, PieceColor::WHITE));
-        board.pieces[Square::new(6, 0).to_index()] = Some(Piece::new(PieceType::Knight, PieceColor::WHITE));
-        board.pieces[Square::new(7, 0).to_index()] = Some(Piece::new(PieceType::Rook,   PieceColor::WHITE));
-
-        board.pieces[Square::new(0, 7).to_index()] = Some(Piece::new(PieceType::Rook,   PieceColor::BLACK));
-        board.pieces[Square::new(1, 7).to_index()] = Some(Piece::new(PieceType::Knight, PieceColor::BLACK));
-        board.pieces[Square::new(2, 7).to_index()] = Some(Piece::new(PieceType::Bishop, PieceColor::BLACK));
-        board.pieces[Square::new(3, 7).to_index()] = Some(Piece::new(PieceType::Queen,  PieceColor::BLACK));
-        board.pieces[Square::new(4, 7).to_index()] = Some(Piece::new(PieceType::King,   PieceColor::BLACK));
-        board.pieces[Square::new(5, 7).to_index()] = Some(Piece::new(PieceType::Bishop, PieceColor::BLACK));
-        board.pieces[Square::new(6, 7).to_index()] = Some(Piece::new(PieceType::Knight, PieceColor::BLACK));
-        board.pieces[Square::new(7, 7).to_index()] = Some(Piece::new(PieceType::Rook,   PieceColor::BLACK));
+        board.put(Square::new(0, 0).to_index(), Some(Piece::new(PieceType::Rook,   PieceColor::WHITE)));
+        board.put(Square::new(1, 0).to_index(), Some(Piece::new(PieceType::Knight, PieceColor::WHITE)));
+        board.put(Square::new(2, 0).to_index(), Some(Piece::new(PieceType::Bishop, PieceColor::WHITE)));
+        board.put(Square::new(3, 0).to_index(), Some(Piece::new(PieceType::Queen,  PieceColor::WHITE)));
+        board.put(Square::new(4, 0).to_index(), Some(Piece::new(PieceType::King,   PieceColor::WHITE)));
+        board.put(Square::new(5, 0).to_index(), Some(Piece::new(PieceType::Bishop, PieceColor::WHITE)));
+        board.put(Square::new(6, 0).to_index(), Some(Piece::new(PieceType::Knight, PieceColor::WHITE)));
+        board.put(Square::new(7, 0).to_index(), Some(Piece::new(PieceType::Rook,   PieceColor::WHITE)));
+
+        board.put(Square::new(0, 7).to_index(), Some(Piece::new(PieceType::Rook,   PieceColor::BLACK)));
+        board.put(Square::new(1, 7).to_index(), Some(Piece::new(PieceType::Knight, PieceColor::BLACK)));
+        board.put(Square::new(2, 7).to_index(), Some(Piece::new(PieceType::Bishop, PieceColor::BLACK)));
+        board.put(Square::new(3, 7).to_index(), Some(Piece::new(PieceType::Queen,  PieceColor::BLACK)));
+        board.put(Square::new(4, 7).to_index(), Some(Piece::new(PieceType::King,   PieceColor::BLACK)));
+        board.put(Square::new(5, 7).to_index(), Some(Piece::new(PieceType::Bishop, PieceColor::BLACK)));
+        board.put(Square::new(6, 7).to_index(), Some(Piece::new(PieceType::Knight, PieceColor::BLACK)));
+        board.put(Square::new(7, 7).to_index(), Some(Piece::new(PieceType::Rook,   PieceColor::BLACK)));
 
         for i in 0..8 {
-            board.pieces[Square::new(i, 1).to_index()] = Some(Piece::new(PieceType::Pawn, PieceColor::WHITE));
-            board.pieces[Square::new(i, 6).to_index()] = Some(Piece::new(PieceType::Pawn, PieceColor::BLACK));
+            board.put(Square::new(i, 1).to_index(), Some(Piece::new(PieceType::Pawn, PieceColor::WHITE)));
+            board.put(Square::new(i, 6).to_index(), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
         }
 
         board
     }
 
-    // basic board state getter functions
-    /// # Returns piece at given index
-    fn get_piece_at_index(&self, index: usize) -> Option<&Piece> {
-        assert!(index < 64);
-        self.pieces[index].as_ref()
-    }
-
-    /// # Returns piece at given square
-    pub fn get_piece(&self, square: Square) -> Option<&Piece> {
-        self.get_piece_at_index(square.to_index())
+    /// # Creates new board with standard starting position
+    ///
+    /// same as `new`
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::new_standard();
+    ///
+    /// assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    /// ```
+    pub fn new_standard() -> Board {
+        Board::new()
     }
 
-    // move simulating board state getter functions
-    /// # Returns piece at given index after simulating move
-    fn get_piece_at_index_after_move(&self, index: usize, sm: Option<Move>) -> Option<&Piece> {
-        match sm {
-            Some(sm) => {
-                if index == sm.start().to_index() {
-                    None
-                } else if index == sm.end().to_index() {
-                    self.get_piece_at_index(sm.start().to_index())    
-                } else {
-                    self.get_piece_at_index(index)
-                }
-            },
-            None => self.get_piece_at_index(index)
+    /// # Creates new Chess960 board with starting position of given Scharnagl number
+    ///
+    /// returns None if `position_id` is not lower than 960, position 518 is the standard starting position,
+    /// castling is written as king moving onto its own rook
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::new_chess960(0).unwrap();
+    ///
+    /// assert!(board.is_chess960());
+    /// assert_eq!(board.to_fen(), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1");
+    /// assert!(Board::new_chess960(960).is_none());
+    /// ```
+    pub fn new_chess960(position_id: u16) -> Option<Board> {
+        if position_id >= 960 {
+            return None;
         }
-    }
 
-    /// # Returns piece at given square after simulating move
-    fn get_piece_after_move(&self, square: Square, sm: Option<Move>) -> Option<&Piece> {
-        self.get_piece_at_index_after_move(square.to_index(), sm)
-    }
+        let mut back_rank = [None; 8];
+        let mut n = position_id as usize;
 
+        // bishops go on light and dark squares
+        back_rank[n % 4 * 2 + 1] = Some(PieceType::Bishop);
+        n /= 4;
+        back_rank[n % 4 * 2] = Some(PieceType::Bishop);
+        n /= 4;
 
+        // places piece on i-th empty square of back rank
+        let mut place = |i: usize, piece_type: PieceType| {
+            let x = (0..8).filter(|x| back_rank[*x].is_none()).nth(i).unwrap();
+            back_rank[x] = Some(piece_type);
+        };
 
+        place(n % 6, PieceType::Queen);
+        n /= 6;
 
-    // move possibility checks
-    fn check_move_possibility_after_move(&self, m: Move, sm: Option<Move>) -> Result<(), MoveFailReason> {
-        let (src, dst) = m.to_squares();
+        let (first, second) = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)][n];
+        place(second, PieceType::Knight);
+        place(first, PieceType::Knight);
 
-        let source_piece = self.get_piece_after_move(src, sm);
-        let destination_piece = self.get_piece_after_move(dst, sm);
+        for piece_type in [PieceType::Rook, PieceType::King, PieceType::Rook] {
+            place(0, piece_type);
+        }
 
-        if let Some(source_piece) = source_piece {
-            let dest_ocuppied = if let Some(destination_piece) = destination_piece {
-                if source_piece.color() == destination_piece.color() {
-                    return Err(MoveFailReason::FriendlyFire);
-                }
-                true
-            } else { false };
+        let mut board = Board::new_clear();
+        board.chess960 = true;
 
-            let (can_move, validate_block) = source_piece.can_move_to(m, dest_ocuppied);
+        for (x, piece_type) in back_rank.into_iter().enumerate() {
+            let piece_type = piece_type.unwrap();
 
-            let move_possible = if validate_block && can_move {
-                LineMovement::from(m).all(|pos| self.get_piece_after_move(pos, sm).is_none())
-            } else { can_move };
+            board.put(Square::new(x as u8, 0).to_index(), Some(Piece::new(piece_type, PieceColor::WHITE)));
+            board.put(Square::new(x as u8, 7).to_index(), Some(Piece::new(piece_type, PieceColor::BLACK)));
+            board.put(Square::new(x as u8, 1).to_index(), Some(Piece::new(PieceType::Pawn, PieceColor::WHITE)));
+            board.put(Square::new(x as u8, 6).to_index(), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
+        }
 
-            if move_possible {
-                if sm.is_some() {
-                    Ok(())
-                } else if self.is_king_attacked_after_move(source_piece.color(), Some(m)) {
-                    Err(MoveFailReason::KingAttacked)
-                } else { Ok(()) }
-            } else { Err(MoveFailReason::IllegalMove) }
-        } else { Err(MoveFailReason::NoSourcePiece) }
+        Some(board)
     }
 
-    /// # 
+    /// # Creates new board from FEN string
+    ///
+    /// piece placement, active color and castling availability fields are required,
+    /// remaining fields are optional
     ///
     /// ```
-    /// # use chess_api::movement::{Move, Square};
     /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::{PieceType, PieceColor};
     ///
-    /// # let board = Board::new();
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
     ///
-    /// assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 1), Square::new(1, 3))).is_ok(), true);
-    /// assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 0), Square::new(1, 3))).is_ok(), false);
+    /// assert_eq!(board.pieces(None).count(), 3);
+    /// assert_eq!(board.get_piece(Square::new(4, 1)).unwrap().piece_type(), PieceType::Pawn);
+    /// assert_eq!(board.get_piece(Square::new(4, 7)).unwrap().color(), PieceColor::BLACK);
+    ///
+    /// assert!(Board::from_fen("4k3/8/8/8/8/8/4K3 w - - 0 1").is_err());
     /// ```
-    pub fn check_move_possibility(&self, m: Move) -> Result<(), MoveFailReason> {
-        self.check_move_possibility_after_move(m, None) 
-    }
+    pub fn from_fen(fen: &str) -> Result<Board, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+
+        if fields.len() < 3 || fields.len() > 6 {
+            return Err(FenError::WrongFieldCount(fields.len()));
+        }
 
+        let mut board = Board::new_clear();
 
+        let ranks: Vec<&str> = fields[0].split('/').collect();
 
+        if ranks.len() != 8 {
+            return Err(FenError::WrongRankCount(ranks.len()));
+        }
 
-    // basic board state changers
-    /// # Sets piece at square
-    ///
-    /// should only be used for setting up custom positions
-    /// not for moving pieces during game
-    ///
-    pub fn set(&mut self, square: Square, piece: Option<Piece>) {
-        self.pieces[square.to_index()] = piece; 
+        for (i, rank) in ranks.iter().enumerate() {
+            let y = 7 - i as u8;
+            let mut x = 0;
+
+            for c in rank.chars() {
+                if let '1'..='8' = c {
+                    let empty = c as u8 - b'0';
+
+                    if x + empty > 8 {
+                        return Err(FenError::WrongFileCount((x + empty) as usize));
+                    }
+
+                    x += empty;
+                } else {
+                    if x >= 8 {
+                        return Err(FenError::WrongFileCount(x as usize + 1));
+                    }
+
+                    let mut piece = Piece::from_char(c).ok_or(FenError::InvalidPiece(c))?;
+
+                    // pawns away from their starting rank can't double push anymore,
+                    // kings and rooks get their flag reset by castling availability
+                    let pawn_rank = if piece.color() == PieceColor::WHITE { 1 } else { 6 };
+
+                    match piece.piece_type() {
+                        PieceType::Pawn if y != pawn_rank => piece.move_piece(),
+                        PieceType::King | PieceType::Rook => piece.move_piece(),
+                        _ => ()
+                    }
+
+                    board.put(Square::new(x, y).to_index(), Some(piece));
+                    x += 1;
+                }
+            }
+
+            if x != 8 {
+                return Err(FenError::WrongFileCount(x as usize));
+            }
+        }
+
+        board.turn = match fields[1] {
+            "w" => PieceColor::WHITE,
+            "b" => PieceColor::BLACK,
+            _ => return Err(FenError::InvalidActiveColor)
+        };
+
+        if fields[2] != "-" {
+            for c in fields[2].chars() {
+                let color = if c.is_ascii_uppercase() { PieceColor::WHITE } else { PieceColor::BLACK };
+
+                // Shredder-FEN and X-FEN name castling rook by its file
+                let granted = match c.to_ascii_lowercase() {
+                    'k' => board.grant_castling(color, true),
+                    'q' => board.grant_castling(color, false),
+                    file @ 'a'..='h' => board.grant_castling_with(color, file as u8 - b'a'),
+                    _ => false
+                };
+
+                if !granted {
+                    return Err(FenError::InvalidCastling(c));
+                }
+            }
+        }
+
+        // en passant square has to lie behind pawn which could have just made double push
+        match fields.get(3) {
+            None | Some(&"-") => (),
+            Some(field) => {
+                let square = Square::from_uci(field).map_err(|_| FenError::InvalidEnPassant)?;
+
+                if !board.is_en_passant_possible(square) {
+                    return Err(FenError::InvalidEnPassant);
+                }
+
+                board.en_passant = Some(square);
+            }
+        }
+
+        if let Some(halfmove_clock) = fields.get(4) {
+            board.halfmove_clock = halfmove_clock.parse().map_err(|_| FenError::InvalidHalfmoveClock)?;
+        }
+
+        if let Some(fullmove_number) = fields.get(5) {
+            board.fullmove_number = fullmove_number.parse().ok()
+                .filter(|number| *number >= 1)
+                .ok_or(FenError::InvalidFullmoveNumber)?;
+        }
+
+        Ok(board)
     }
 
-    // advanced board state changers
-    /// # Performs move after checking if it is possible
-    ///
-    /// ```
-    /// # use chess_api::movement::{Move, Square};
-    /// # use chess_api::piece::{PieceType, PieceColor};
-    /// # use chess_api::board::Board;
-    ///
-    /// let mut board = Board::new();
+    /// # Marks king and outermost rook on given side as unmoved so given color can castle with them
     ///
-    /// board.perform_move(Move::new(Square::new(1, 1), Square::new(1, 3)));
+    /// returns false if there is no king or no such rook on home rank
+    fn grant_castling(&mut self, color: PieceColor, kingside: bool) -> bool {
+        match self.outermost_rook_file(color, kingside) {
+            Some(rook_x) => self.grant_castling_with(color, rook_x),
+            None => false
+        }
+    }
+
+    /// # Marks king and rook on given file as unmoved so given color can castle with them
     ///
-    /// assert_eq!(board.get_piece(Square::new(1, 3)).unwrap().piece_type(), PieceType::Pawn);
-    /// assert_eq!(board.get_piece(Square::new(1, 3)).unwrap().color(), PieceColor::WHITE);
-    /// assert!(board.get_piece(Square::new(1, 1)).is_none());
-    /// ```
-    pub fn perform_move(&mut self, m: Move) -> Result<PostMoveState, MoveFailReason> {
-        match self.check_move_possibility(m) {
-            Ok(_) => {
-                let src = m.start().to_index();
-                let dst = m.end().to_index();
-
-                self.pieces[dst] = self.pieces[src].take();
-                self.pieces[dst].as_mut().unwrap().move_piece();
-
-                let next_color = !self.pieces[dst].as_ref().unwrap().color();
-                let king_attacked = self.is_king_attacked(next_color);
-                let has_moves = self.all_possible_moves(Some(next_color)).next().is_some();
-
-                if king_attacked && has_moves {
-                    Ok(PostMoveState::Check)
-                } else if king_attacked {
-                    Ok(PostMoveState::Checkmate)
-                } else if has_moves {
-                    Ok(PostMoveState::Normal)
-                } else {
-                    Ok(PostMoveState::Stelemate)
+    /// returns false if there is no king or no such rook on home rank,
+    /// board switches to Chess960 castling unless king is on e file and rook in the corner
+    fn grant_castling_with(&mut self, color: PieceColor, rook_x: u8) -> bool {
+        let y = if color == PieceColor::WHITE { 0 } else { 7 };
+        let king_x = self.home_piece_files(color, PieceType::King).next();
+
+        match king_x {
+            Some(king_x) if self.home_piece_files(color, PieceType::Rook).any(|x| x == rook_x) => {
+                for x in [king_x, rook_x] {
+                    self.pieces[Square::new(x, y).to_index()].as_mut().unwrap().reset_moved();
                 }
+
+                if king_x != 4 || (rook_x != 0 && rook_x != 7) {
+                    self.chess960 = true;
+                }
+
+                true
             },
-            Err(e) => Err(e)
+            _ => false
         }
     }
 
-    // iterators
-    fn squares_after_move(&self, sm: Option<Move>) -> impl Iterator<Item = (Square, Option<&Piece>)> {
-        (0..64).map(|index| Square::from_index(index)).map(move |square| (square, self.get_piece_after_move(square, sm)))
+    /// # Returns file of rook on home rank farthest from the king on given side, moved or not
+    fn outermost_rook_file(&self, color: PieceColor, kingside: bool) -> Option<u8> {
+        let king_x = self.home_piece_files(color, PieceType::King).next()?;
+        let mut rooks = self.home_piece_files(color, PieceType::Rook);
+
+        if kingside {
+            rooks.filter(|x| *x > king_x).last()
+        } else { rooks.find(|x| *x < king_x) }
     }
 
-    /// # Returns iterator over every square on the board
-    pub fn squares(&self) -> impl Iterator<Item = (Square, Option<&Piece>)> {
-        self.squares_after_move(None)
+    /// # Returns files of given color's pieces of given type standing on its home rank, from a to h
+    fn home_piece_files(&self, color: PieceColor, piece_type: PieceType) -> impl Iterator<Item = u8> + '_ {
+        let y = if color == PieceColor::WHITE { 0 } else { 7 };
+
+        (0..8).filter(move |x| matches!(self.get_piece(Square::new(*x, y)),
+            Some(piece) if piece.piece_type() == piece_type && piece.color() == color))
     }
 
-    /// # Returns iterator over every piece on the board
+    /// # Returns FEN string describing the board
     ///
     /// ```
     /// # use chess_api::board::Board;
-    /// 
-    /// # let board = Board::new();
     ///
-    /// for (square, piece) in board.pieces(None) {
-    ///     println!("{:?} => {:?}", square, piece);
-    /// }
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
     /// ```
-    fn pieces_after_move(&self, color: Option<PieceColor>, sm: Option<Move>) -> impl Iterator<Item = (Square, &Piece)> {
-        self.squares_after_move(sm).filter_map(|square| match square.1 {
-            Some(piece) => Some((square.0, piece)),
-            None => None
-        }).filter(move |piece| match color {
-            Some(color) => piece.1.color() == color,
-            None => true
-        })
-    }
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
 
-    pub fn pieces(&self, color: Option<PieceColor>) -> impl Iterator<Item = (Square, &Piece)> {
-        self.pieces_after_move(color, None)
-    }
+        for y in (0..8).rev() {
+            let mut empty = 0;
 
-    // advanced board state getters
-    /// # Returns true if given square is attacked by given player after simulating move
-    fn is_square_attacked_after_move(&self, square: Square, color: PieceColor, sm: Option<Move>) -> bool {
-        self.pieces_after_move(Some(color), sm).filter(|(start, _)| *start != square).any(|(start, _)| self.check_move_possibility_after_move(Move::new(start, square), sm).is_ok())
+            for x in 0..8 {
+                match self.get_piece(Square::new(x, y)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push(piece.get_character());
+                    },
+                    None => empty += 1
+                }
+            }
+
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+
+            if y > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push_str(match self.turn {
+            PieceColor::WHITE => " w ",
+            PieceColor::BLACK => " b "
+        });
+
+        let castling: String = [(PieceColor::WHITE, true), (PieceColor::WHITE, false), (PieceColor::BLACK, true), (PieceColor::BLACK, false)]
+            .into_iter()
+            .filter_map(|(color, kingside)| self.castling_char(color, kingside))
+            .collect();
+
+        fen.push_str(if castling.is_empty() { "-" } else { &castling });
+        let en_passant = self.en_passant.map_or("-".to_string(), |square| square.to_uci());
+        fen.push_str(&format!(" {} {} {}", en_passant, self.halfmove_clock, self.fullmove_number));
+
+        fen
     }
 
-    /// # Returns true if given square is attacked by given player
+    /// # Returns character of castling right in FEN, or None if it is not available
     ///
-    /// will return false if attacked only by allied piece
+    /// Chess960 castling with other than outermost rook is written with rook's file as in X-FEN
+    fn castling_char(&self, color: PieceColor, kingside: bool) -> Option<char> {
+        let rook = self.castling_rook_square(color, kingside)?;
+
+        let c = match (self.outermost_rook_file(color, kingside) == Some(rook.x()), kingside) {
+            (true, true) => 'k',
+            (true, false) => 'q',
+            (false, _) => rook.file()
+        };
+
+        Some(if color == PieceColor::WHITE { c.to_ascii_uppercase() } else { c })
+    }
+
+    /// # Returns color which is about to move
     ///
     /// ```
     /// # use chess_api::board::Board;
-    /// # use chess_api::movement::Square;
+    /// # use chess_api::movement::Move;
     /// # use chess_api::piece::PieceColor;
     ///
     /// let mut board = Board::new();
     ///
-    /// board.set(Square::new(3, 1), None); // d2 ( in front of white's queen )
-    /// 
-    /// assert_eq!(board.is_square_attacked(Square::new(3, 6), PieceColor::WHITE), true); // d7
-    /// assert_eq!(board.is_square_attacked(Square::new(3, 6), PieceColor::BLACK), false);
-    /// assert_eq!(board.is_square_attacked(Square::new(3, 1), PieceColor::WHITE), true);
-    /// assert_eq!(board.is_square_attacked(Square::new(3, 1), PieceColor::BLACK), false);
+    /// assert_eq!(board.turn(), PieceColor::WHITE);
+    /// assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+    /// assert_eq!(board.turn(), PieceColor::BLACK);
     /// ```
-    pub fn is_square_attacked(&self, square: Square, color: PieceColor) -> bool {
-        self.is_square_attacked_after_move(square, color, None)
-    }
-
-    /// # If king is attacked returns true
-    fn is_king_attacked_after_move(&self, color: PieceColor, sm: Option<Move>) -> bool {
-        if let Some((square, _)) = self.pieces_after_move(Some(color), sm).find(|(_, piece)| piece.piece_type() == PieceType::King) {
-            self.is_square_attacked_after_move(square, !color, sm)
-        } else { false }
+    pub fn turn(&self) -> PieceColor {
+        self.turn
     }
 
-    /// # If king is attacked returns true
-    ///
-    /// will return false if attacked only by allied piece
+    /// # Returns number of halfmoves since the last capture or pawn move
     ///
     /// ```
     /// # use chess_api::board::Board;
-    /// # use chess_api::movement::{Move, Square};
-    /// # use chess_api::piece::PieceColor;
+    /// # use chess_api::movement::Move;
     ///
     /// let mut board = Board::new();
     ///
-    /// assert!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok()); // e4
-    /// assert!(board.perform_move(Move::new(Square::new(5, 6), Square::new(5, 4))).is_ok()); // f6
-    /// assert!(board.perform_move(Move::new(Square::new(3, 0), Square::new(7, 4))).is_ok()); // Qh5
-    ///
-    /// assert_eq!(board.is_king_attacked(PieceColor::WHITE), false);
-    /// assert_eq!(board.is_king_attacked(PieceColor::BLACK), true);
+    /// assert!(board.apply_move(Move::from_uci("g1f3").unwrap()).is_ok());
+    /// assert_eq!(board.halfmove_clock(), 1);
+    /// assert!(board.apply_move(Move::from_uci("e7e5").unwrap()).is_ok());
+    /// assert_eq!(board.halfmove_clock(), 0);
     /// ```
-    pub fn is_king_attacked(&self, color: PieceColor) -> bool {
-        self.is_king_attacked_after_move(color, None)
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
     }
 
-    /// # Returns iterator for every possible move from given square
+    /// # Returns number of the current full move
     ///
-    /// move order is not defined and may change in future
+    /// starts at 1 and is incremented after each black move
     ///
     /// ```
     /// # use chess_api::board::Board;
-    /// # use chess_api::movement::{Move, Square};
-    /// # use chess_api::piece::PieceColor;
+    /// # use chess_api::movement::Move;
     ///
     /// let mut board = Board::new();
     ///
-    /// assert!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok()); // e4
-    /// 
+    /// assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+    /// assert_eq!(board.fullmove_number(), 1);
+    /// assert!(board.apply_move(Move::from_uci("e7e5").unwrap()).is_ok());
+    /// assert_eq!(board.fullmove_number(), 2);
+    /// ```
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    /// # Returns true if game can be drawn by the fifty-move rule
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 99 80").unwrap().is_fifty_move_draw(), false);
+    /// assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 100 80").unwrap().is_fifty_move_draw(), true);
+    /// ```
+    pub fn is_fifty_move_draw(&self) -> bool {
+        self.halfmove_clock >= 100
+    }
+
+    /// # Returns true if neither side has enough material to checkmate
+    ///
+    /// covers king against king, king and minor piece against king
+    /// and kings with bishops all standing on squares of the same color
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap().is_insufficient_material(), true);
+    /// assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap().is_insufficient_material(), false);
+    /// ```
+    pub fn is_insufficient_material(&self) -> bool {
+        let pieces: Vec<(Square, &Piece)> = self.pieces(None).filter(|(_, piece)| piece.piece_type() != PieceType::King).collect();
+
+        match pieces.as_slice() {
+            [] => true,
+            [(_, piece)] => matches!(piece.piece_type(), PieceType::Bishop | PieceType::Knight),
+            [(first, _), ..] => pieces.iter().all(|(square, piece)| {
+                piece.piece_type() == PieceType::Bishop && square.is_light() == first.is_light()
+            })
+        }
+    }
+
+    /// # Returns true if given side alone has enough material to checkmate
+    ///
+    /// lone king and king with single minor piece are not enough, used when opponent runs out of time
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/4P3/2N1K3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.has_mating_material(PieceColor::WHITE), true);
+    /// assert_eq!(board.has_mating_material(PieceColor::BLACK), false);
+    /// ```
+    pub fn has_mating_material(&self, color: PieceColor) -> bool {
+        let counts = self.material_count(color);
+        let minors = counts[PieceType::Bishop as usize] + counts[PieceType::Knight as usize];
+
+        counts[PieceType::Pawn as usize] > 0 || counts[PieceType::Rook as usize] > 0 || counts[PieceType::Queen as usize] > 0 || minors > 1
+    }
+
+    /// # Checks position for problems making it unreachable or unplayable
+    ///
+    /// every problem found is reported: missing king or more than one king of a side,
+    /// pawns on first or last rank, side not to move being in check and en passant square
+    /// not lying behind pawn which just made double push
+    ///
+    /// ```
+    /// # use chess_api::board::{Board, PositionError};
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// assert_eq!(Board::new().validate(), Ok(()));
+    /// assert_eq!(Board::from_fen("8/8/8/8/8/8/8/4K3 w - - 0 1").unwrap().validate(), Err(vec![PositionError::MissingKing(PieceColor::BLACK)]));
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<PositionError>> {
+        let mut errors = Vec::new();
+
+        for color in PieceColor::ALL {
+            match self.bitboards[bitboard::piece_index(PieceType::King, color)].count_ones() {
+                0 => errors.push(PositionError::MissingKing(color)),
+                1 => (),
+                _ => errors.push(PositionError::TooManyKings(color))
+            }
+        }
+
+        errors.extend(self.pieces(None)
+            .filter(|(square, piece)| piece.piece_type() == PieceType::Pawn && (square.y() == 0 || square.y() == 7))
+            .map(|(square, _)| PositionError::PawnOnBackRank(square)));
+
+        if self.is_in_check(!self.turn) {
+            errors.push(PositionError::OpponentInCheck);
+        }
+
+        if let Some(square) = self.en_passant.filter(|square| !self.is_en_passant_possible(*square)) {
+            errors.push(PositionError::InvalidEnPassant(square));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else { Err(errors) }
+    }
+
+    /// # Returns true if given square could have been skipped by opponent's last double pawn push
+    fn is_en_passant_possible(&self, square: Square) -> bool {
+        let (rank, pawn_rank) = if self.turn == PieceColor::WHITE { (5, 4) } else { (2, 3) };
+        let pushed = self.piece_at(square.x(), pawn_rank).is_some_and(|piece| piece.piece_type() == PieceType::Pawn && piece.color() != self.turn);
+
+        square.y() == rank && pushed && self.get_piece(square).is_none()
+    }
+
+    /// # Returns square skipped by the last double pawn push
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::{Move, Square};
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// assert_eq!(board.en_passant(), None);
+    /// assert!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok()); // e4
+    /// assert_eq!(board.en_passant(), Some(Square::new(4, 2)));
+    /// ```
+    pub fn en_passant(&self) -> Option<Square> {
+        self.en_passant
+    }
+
+    /// # Returns the last move played, or None if no moves were played yet
+    ///
+    /// follows `undo`, taken back move is no longer reported
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Move;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// assert_eq!(board.last_move(), None);
+    /// assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+    /// assert_eq!(board.last_move(), Some(Move::from_uci("e2e4").unwrap()));
+    /// ```
+    pub fn last_move(&self) -> Option<Move> {
+        self.history.last().map(|entry| entry.m)
+    }
+
+    /// # Returns state of the game in current position
+    ///
+    /// meant to be called after `apply_move`, checkmate and stalemate take precedence over draws
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::board::{Board, GameState};
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// assert_eq!(board.state(), GameState::Ongoing);
+    ///
+    /// for m in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+    ///     assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+    /// }
+    ///
+    /// assert_eq!(board.state(), GameState::Checkmate(PieceColor::BLACK));
+    /// ```
+    pub fn state(&self) -> GameState {
+        let in_check = self.is_in_check(self.turn);
+        let has_moves = self.all_possible_moves(Some(self.turn)).next().is_some();
+
+        if !has_moves {
+            if in_check { GameState::Checkmate(!self.turn) } else { GameState::Stalemate }
+        } else if self.is_threefold_repetition() {
+            GameState::DrawByRepetition
+        } else if self.is_fifty_move_draw() {
+            GameState::DrawByFiftyMove
+        } else if self.is_insufficient_material() {
+            GameState::DrawByInsufficientMaterial
+        } else if in_check {
+            GameState::Check(self.turn)
+        } else {
+            GameState::Ongoing
+        }
+    }
+
+    /// # Returns how the game ended in current position, or None if play continues
+    ///
+    /// same precedence as `state`, checkmate and stalemate take precedence over draws
+    ///
+    /// ```
+    /// # use chess_api::board::{Board, Outcome};
+    ///
+    /// assert_eq!(Board::new().outcome(), None);
+    /// assert_eq!(Board::from_fen("7k/8/6QK/8/8/8/8/8 b - - 0 1").unwrap().outcome(), Some(Outcome::Stalemate));
+    /// ```
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self.state() {
+            GameState::Ongoing | GameState::Check(_) => None,
+            GameState::Checkmate(winner) => Some(Outcome::Checkmate(winner)),
+            GameState::Stalemate => Some(Outcome::Stalemate),
+            GameState::DrawByRepetition => Some(Outcome::DrawByRepetition),
+            GameState::DrawByFiftyMove => Some(Outcome::DrawByFiftyMove),
+            GameState::DrawByInsufficientMaterial => Some(Outcome::DrawByInsufficientMaterial)
+        }
+    }
+
+    /// # Returns material balance in centipawns
+    ///
+    /// sum of white pieces' values minus sum of black pieces' values, see `PieceType::value`
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// assert_eq!(Board::new().material_balance(), 0);
+    /// assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap().material_balance(), 500);
+    /// ```
+    pub fn material_balance(&self) -> i32 {
+        self.evaluate_with(&EvalParams::default())
+    }
+
+    /// # Returns evaluation of the position with given weights, from white's point of view
+    ///
+    /// sums piece values and structure terms of white and subtracts those of black,
+    /// doubled pawn term counts every pawn behind another one on its file,
+    /// isolated pawn term counts every pawn with no own pawns on neighbouring files
+    ///
+    /// ```
+    /// # use chess_api::board::{Board, EvalParams};
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/4P3/2B1KB2 w - - 0 1").unwrap();
+    /// let params = EvalParams { bishop_pair: 50, isolated_pawn: -20, ..EvalParams::default() };
+    ///
+    /// assert_eq!(board.evaluate_with(&params), 100 + 2 * 330 + 50 - 20);
+    /// ```
+    pub fn evaluate_with(&self, params: &EvalParams) -> i32 {
+        const FILE_A: u64 = 0x0101_0101_0101_0101;
+
+        PieceColor::ALL.into_iter().map(|color| {
+            let count = |piece_type: PieceType| self.bitboards[bitboard::piece_index(piece_type, color)].count_ones() as i32;
+            let pawns = self.bitboards[bitboard::piece_index(PieceType::Pawn, color)];
+
+            let mut score: i32 = PieceType::ALL.into_iter().map(|piece_type| params.piece_values[piece_type as usize] * count(piece_type)).sum();
+
+            if count(PieceType::Bishop) >= 2 {
+                score += params.bishop_pair;
+            }
+
+            for file in 0..8 {
+                let on_file = (pawns & FILE_A << file).count_ones() as i32;
+                let neighbours = (if file > 0 { FILE_A << (file - 1) } else { 0 }) | (if file < 7 { FILE_A << (file + 1) } else { 0 });
+
+                score += params.doubled_pawn * (on_file - 1).max(0);
+
+                if pawns & neighbours == 0 {
+                    score += params.isolated_pawn * on_file;
+                }
+            }
+
+            if color == PieceColor::WHITE { score } else { -score }
+        }).sum()
+    }
+
+    /// # Returns squares of all pieces of given type and color, from a1 to h8
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::{PieceColor, PieceType};
+    ///
+    /// assert_eq!(Board::new().find_pieces(PieceType::Queen, PieceColor::BLACK).len(), 1);
+    /// ```
+    pub fn find_pieces(&self, piece_type: PieceType, color: PieceColor) -> Vec<Square> {
+        bitboard::indices(self.bitboards[bitboard::piece_index(piece_type, color)]).map(|i| Square::from_index(i).unwrap()).collect()
+    }
+
+    /// # Returns number of pieces on board, both colors and kings included
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// assert_eq!(Board::new().piece_count(), 32);
+    /// assert_eq!(Board::new_clear().piece_count(), 0);
+    /// ```
+    pub fn piece_count(&self) -> usize {
+        self.occupancy(None).count_ones() as usize
+    }
+
+    /// # Returns number of pieces of each type given color has on board
+    ///
+    /// counts are indexed by `piece_type as usize`
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::{PieceColor, PieceType};
+    ///
+    /// let counts = Board::new().material_count(PieceColor::WHITE);
+    ///
+    /// assert_eq!(counts[PieceType::Pawn as usize], 8);
+    /// assert_eq!(counts[PieceType::Queen as usize], 1);
+    /// ```
+    pub fn material_count(&self, color: PieceColor) -> [u8; 6] {
+        let mut counts = [0; 6];
+
+        for piece_type in PieceType::ALL {
+            counts[piece_type as usize] = self.bitboards[bitboard::piece_index(piece_type, color)].count_ones() as u8;
+        }
+
+        counts
+    }
+
+    /// # Returns Zobrist hash of the position
+    ///
+    /// hash depends on piece placement, side to move, castling availability and en passant square,
+    /// equal boards always have equal hashes
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+    /// assert_ne!(board.zobrist_hash(), Board::new().zobrist_hash());
+    ///
+    /// board.undo();
+    /// assert_eq!(board.zobrist_hash(), Board::new().zobrist_hash());
+    /// ```
+    pub fn zobrist_hash(&self) -> u64 {
+        let mut hash = self.pieces(None).fold(0, |hash, (square, piece)| hash ^ zobrist::piece_key(piece.piece_type(), piece.color(), square.to_index()));
+
+        if self.turn == PieceColor::BLACK {
+            hash ^= zobrist::side_key();
+        }
+
+        for (i, available) in self.castling_availability().into_iter().enumerate() {
+            if available {
+                hash ^= zobrist::castling_key(i);
+            }
+        }
+
+        if let Some(square) = self.en_passant {
+            hash ^= zobrist::en_passant_key(square.to_coords().0);
+        }
+
+        hash
+    }
+
+    /// # Returns key identifying position for repetition detection
+    ///
+    /// like `zobrist_hash` it ignores halfmove clock, but en passant square is included
+    /// only when en passant capture is legal, as required by the rules for repeated positions
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut board = Board::new();
+    /// let same = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 5 1").unwrap();
+    ///
+    /// // no black pawn can capture on e3
+    /// assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+    /// assert_ne!(board.zobrist_hash(), same.zobrist_hash());
+    /// assert_eq!(board.repetition_key(), same.repetition_key());
+    /// ```
+    pub fn repetition_key(&self) -> u64 {
+        let mut key = self.zobrist_hash();
+
+        if let Some(square) = self.en_passant {
+            let pawns = bitboard::pawn_attacks(!self.turn, square.to_index()) & self.bitboards[bitboard::piece_index(PieceType::Pawn, self.turn)];
+            let capturable = bitboard::indices(pawns).any(|i| self.check_move_possibility(Move::new(Square::from_index(i).unwrap(), square)).is_ok());
+
+            if !capturable {
+                key ^= zobrist::en_passant_key(square.x());
+            }
+        }
+
+        key
+    }
+
+    /// # Returns true if current position has occurred at least three times
+    ///
+    /// positions are the same when they have the same repetition key, see `repetition_key`
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// for m in ["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1"] {
+    ///     assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+    ///     assert_eq!(board.is_threefold_repetition(), false);
+    /// }
+    ///
+    /// assert!(board.apply_move(Move::from_uci("f6g8").unwrap()).is_ok());
+    /// assert_eq!(board.is_threefold_repetition(), true);
+    /// ```
+    pub fn is_threefold_repetition(&self) -> bool {
+        let position = self.repetition_key();
+
+        self.history.iter().filter(|entry| entry.position == position).count() >= 2
+    }
+
+    /// # Returns castling rights of both players
+    ///
+    /// right is kept while king and corresponding rook haven't moved, so it is lost
+    /// when either of them moves or the rook is captured
+    ///
+    /// ```
+    /// # use chess_api::board::{Board, CastlingRights};
+    /// # use chess_api::movement::Move;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// assert_eq!(board.castling_rights(), CastlingRights::ALL);
+    ///
+    /// for m in ["e2e4", "e7e5", "e1e2"] {
+    ///     assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+    /// }
+    ///
+    /// assert_eq!(board.castling_rights(), CastlingRights::BLACK_KINGSIDE | CastlingRights::BLACK_QUEENSIDE);
+    /// ```
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.castling_availability().into_iter().enumerate()
+            .filter(|(_, available)| *available)
+            .fold(CastlingRights::NONE, |rights, (i, _)| rights | CastlingRights(1 << i))
+    }
+
+    /// # Returns castling availability in `KQkq` order
+    ///
+    /// castling is available when king and corresponding rook haven't moved
+    fn castling_availability(&self) -> [bool; 4] {
+        [(PieceColor::WHITE, true), (PieceColor::WHITE, false), (PieceColor::BLACK, true), (PieceColor::BLACK, false)]
+            .map(|(color, kingside)| self.castling_rook_square(color, kingside).is_some())
+    }
+
+    /// # Returns square of rook given color could still castle with on given side
+    ///
+    /// in standard chess king has to be on e file and rook in the corner,
+    /// in Chess960 outermost unmoved rook on king's side is used
+    fn castling_rook_square(&self, color: PieceColor, kingside: bool) -> Option<Square> {
+        let y = if color == PieceColor::WHITE { 0 } else { 7 };
+        let unmoved = |x: &u8, piece_type| matches!(self.get_piece(Square::new(*x, y)),
+            Some(piece) if piece.piece_type() == piece_type && piece.color() == color && !piece.has_moved());
+
+        let (king_x, rook_x) = if self.chess960 {
+            let king_x = (0..8).find(|x| unmoved(x, PieceType::King))?;
+            let rook_x = if kingside {
+                (king_x + 1..8).rev().find(|x| unmoved(x, PieceType::Rook))
+            } else {
+                (0..king_x).find(|x| unmoved(x, PieceType::Rook))
+            };
+
+            (king_x, rook_x?)
+        } else {
+            (4, if kingside { 7 } else { 0 })
+        };
+
+        if unmoved(&king_x, PieceType::King) && unmoved(&rook_x, PieceType::Rook) {
+            Some(Square::new(rook_x, y))
+        } else { None }
+    }
+
+    /// # Returns true if board is set up for Chess960
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// assert_eq!(Board::new().is_chess960(), false);
+    /// assert_eq!(Board::new_chess960(518).unwrap().is_chess960(), true);
+    /// ```
+    pub fn is_chess960(&self) -> bool {
+        self.chess960
+    }
+
+    // basic board state getter functions
+    /// # Returns piece at given index
+    fn get_piece_at_index(&self, index: usize) -> Option<&Piece> {
+        assert!(index < 64);
+        self.pieces[index].as_ref()
+    }
+
+    /// # Returns piece at given square
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::{PieceType, PieceColor};
+    ///
+    /// let board = Board::new();
+    /// let piece = board.get_piece(Square::from_uci("d8").unwrap()).unwrap();
+    ///
+    /// assert_eq!(piece.piece_type(), PieceType::Queen);
+    /// assert_eq!(piece.color(), PieceColor::BLACK);
+    /// assert!(board.get_piece(Square::from_uci("d4").unwrap()).is_none());
+    /// ```
+    pub fn get_piece(&self, square: Square) -> Option<&Piece> {
+        self.get_piece_at_index(square.to_index())
+    }
+
+    /// # Returns piece at given coordinates
+    ///
+    /// note: `x` and `y` are 0 based, returns None for coordinates out of board
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::PieceType;
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.piece_at(4, 0).unwrap().piece_type(), PieceType::King);
+    /// assert!(board.piece_at(4, 4).is_none());
+    /// assert!(board.piece_at(8, 0).is_none());
+    /// ```
+    pub fn piece_at(&self, x: u8, y: u8) -> Option<&Piece> {
+        self.get_piece(Square::try_new(x, y).ok()?)
+    }
+
+    // move simulating board state getter functions
+    /// # Returns piece at given index after simulating move
+    fn get_piece_at_index_after_move(&self, index: usize, sm: Option<Move>) -> Option<&Piece> {
+        match sm {
+            Some(sm) => {
+                if index == sm.start().to_index() || self.en_passant_capture_square(sm).map(|square| square.to_index()) == Some(index) {
+                    None
+                } else if index == sm.end().to_index() {
+                    self.get_piece_at_index(sm.start().to_index())    
+                } else {
+                    self.get_piece_at_index(index)
+                }
+            },
+            None => self.get_piece_at_index(index)
+        }
+    }
+
+    /// # Returns bitboard of squares occupied by pieces of given color, or by all pieces
+    fn occupancy(&self, color: Option<PieceColor>) -> u64 {
+        match color {
+            Some(color) => self.bitboards[bitboard::piece_index(PieceType::Pawn, color)..][..6].iter().fold(0, |acc, bb| acc | bb),
+            None => self.bitboards.iter().fold(0, |acc, bb| acc | bb)
+        }
+    }
+
+    /// # Returns bitboard of occupied squares after simulating move
+    fn occupancy_after_move(&self, sm: Option<Move>) -> u64 {
+        let occupancy = self.occupancy(None);
+
+        match sm {
+            Some(sm) => {
+                let captured = self.en_passant_capture_square(sm).map_or(0, |square| bitboard::bit(square.to_index()));
+                (occupancy & !bitboard::bit(sm.start().to_index()) & !captured) | bitboard::bit(sm.end().to_index())
+            },
+            None => occupancy
+        }
+    }
+
+    /// # Returns piece at given square after simulating move
+    fn get_piece_after_move(&self, square: Square, sm: Option<Move>) -> Option<&Piece> {
+        self.get_piece_at_index_after_move(square.to_index(), sm)
+    }
+
+    // move possibility checks
+    /// # Returns true if squares between move's start and end are empty after simulating move
+    fn is_path_clear_after_move(&self, m: Move, sm: Option<Move>) -> bool {
+        if m.is_straight() || m.is_diagonal() {
+            bitboard::between(m.start().to_index(), m.end().to_index()) & self.occupancy_after_move(sm) == 0
+        } else {
+            LineMovement::from(m).all(|square| self.get_piece_after_move(square, sm).is_none())
+        }
+    }
+
+    /// # Returns true if squares between move's start and end are empty
+    ///
+    /// start and end are not checked, only meaningful for moves along a file, rank or diagonal,
+    /// used to validate moves of sliding pieces
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Move;
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.is_path_clear(Move::from_uci("a1a3").unwrap()), false);
+    /// assert_eq!(board.is_path_clear(Move::from_uci("a2a7").unwrap()), true);
+    /// ```
+    pub fn is_path_clear(&self, m: Move) -> bool {
+        self.is_path_clear_after_move(m, None)
+    }
+
+    /// # Checks if move is possible after simulating move, ignoring safety of mover's king
+    fn check_pseudo_move_possibility_after_move(&self, m: Move, sm: Option<Move>) -> Result<(), MoveFailReason> {
+        let (src, dst) = m.to_squares();
+
+        let source_piece = self.get_piece_after_move(src, sm);
+        let destination_piece = self.get_piece_after_move(dst, sm);
+
+        if let Some(source_piece) = source_piece {
+            let promotes = source_piece.piece_type() == PieceType::Pawn && is_last_rank(dst, source_piece.color());
+
+            // pawn reaching last rank has to name its promotion piece
+            let promotion_valid = match m.promotion() {
+                Some(promotion) => promotes && promotion != PieceType::King && promotion != PieceType::Pawn,
+                None => !promotes
+            };
+
+            if !promotion_valid {
+                return Err(MoveFailReason::IllegalPromotion);
+            }
+
+            let dest_ocuppied = if let Some(destination_piece) = destination_piece {
+                if source_piece.color() == destination_piece.color() {
+                    return Err(MoveFailReason::FriendlyFire);
+                }
+                true
+            } else { sm.is_none() && self.en_passant_capture_square(m).is_some() };
+
+            // pieces only check geometry, board checks that sliding piece's path is clear,
+            // castling is validated separately
+            let move_possible = match source_piece.move_kind(m, dest_ocuppied) {
+                MoveKind::Illegal | MoveKind::Castle => false,
+                MoveKind::Sliding | MoveKind::PawnDouble => self.is_path_clear_after_move(m, sm),
+                _ => true
+            };
+
+            if move_possible { Ok(()) } else { Err(MoveFailReason::IllegalMove) }
+        } else { Err(MoveFailReason::NoSourcePiece) }
+    }
+
+    /// # Returns true if piece on move's start can make the move, ignoring safety of its king
+    ///
+    /// pseudo-legal check: geometry, clear path, captures and promotions are validated,
+    /// side to move is not, see `is_legal` for full legality check
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::from_fen("4r1k1/8/8/8/8/8/4R3/4K3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.is_move_possible(Move::from_uci("e2a2").unwrap()), true);
+    /// assert_eq!(board.is_move_possible(Move::from_uci("e2f3").unwrap()), false);
+    /// ```
+    pub fn is_move_possible(&self, m: Move) -> bool {
+        if m.start() == m.end() {
+            return false;
+        }
+
+        match self.castling(m) {
+            Some(castling) => self.check_castling_possibility(castling).is_ok(),
+            None => self.check_pseudo_move_possibility_after_move(m, None).is_ok()
+        }
+    }
+
+    /// # Returns true if move is legal in current position
+    ///
+    /// move has to be made by side to move and must not leave its king attacked
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::from_fen("4r1k1/8/8/8/8/8/4R3/4K3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.is_legal(Move::from_uci("e2e8").unwrap()), true);
+    /// assert_eq!(board.is_legal(Move::from_uci("e2a2").unwrap()), false);
+    /// assert_eq!(board.is_legal(Move::from_uci("g8g7").unwrap()), false);
+    /// ```
+    pub fn is_legal(&self, m: Move) -> bool {
+        self.get_piece(m.start()).is_some_and(|piece| piece.color() == self.turn) && self.check_move_possibility(m).is_ok()
+    }
+
+    /// # Checks if move is possible
+    ///
+    /// null move, where start and end are the same square, is never possible,
+    /// side to move is not checked
+    ///
+    /// ```
+    /// # use chess_api::movement::{Move, Square};
+    /// # use chess_api::board::Board;
+    ///
+    /// # let board = Board::new();
+    ///
+    /// assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 1), Square::new(1, 3))).is_ok(), true);
+    /// assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 0), Square::new(1, 3))).is_ok(), false);
+    /// assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 1), Square::new(1, 1))).is_ok(), false);
+    /// ```
+    pub fn check_move_possibility(&self, m: Move) -> Result<(), MoveFailReason> {
+        if m.start() == m.end() {
+            return Err(MoveFailReason::IllegalMove);
+        }
+
+        if let Some(castling) = self.castling(m) {
+            return self.check_castling_possibility(castling);
+        }
+
+        self.check_pseudo_move_possibility_after_move(m, None)?;
+
+        let color = self.get_piece(m.start()).unwrap().color();
+
+        if self.is_king_attacked_after_move(color, Some(m)) {
+            Err(MoveFailReason::KingAttacked)
+        } else { Ok(()) }
+    }
+
+    /// # Returns reason why given move can't be played now, or None if move is legal
+    ///
+    /// meant for explaining rejected moves to the user, castling which is no longer available
+    /// is reported as `PieceCannotMoveThatWay`
+    ///
+    /// ```
+    /// # use chess_api::board::{Board, IllegalReason};
+    /// # use chess_api::movement::Move;
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.explain_illegal(Move::from_uci("e2e4").unwrap()), None);
+    /// assert_eq!(board.explain_illegal(Move::from_uci("a1a3").unwrap()), Some(IllegalReason::PathBlocked));
+    /// assert_eq!(board.explain_illegal(Move::from_uci("e7e5").unwrap()), Some(IllegalReason::WrongTurn));
+    /// ```
+    pub fn explain_illegal(&self, m: Move) -> Option<IllegalReason> {
+        let piece = match self.get_piece(m.start()) {
+            Some(piece) => piece,
+            None => return Some(IllegalReason::NoPieceAtStart)
+        };
+
+        if piece.color() != self.turn {
+            return Some(IllegalReason::WrongTurn);
+        }
+
+        let reason = match self.check_move_possibility(m).err()? {
+            MoveFailReason::NoSourcePiece => IllegalReason::NoPieceAtStart,
+            MoveFailReason::WrongTurn => IllegalReason::WrongTurn,
+            MoveFailReason::FriendlyFire => IllegalReason::CapturingOwnPiece,
+            MoveFailReason::KingAttacked => IllegalReason::LeavesKingInCheck,
+            MoveFailReason::IllegalPromotion => IllegalReason::InvalidPromotion,
+            MoveFailReason::IllegalMove => {
+                let dest_occupied = self.get_piece(m.end()).is_some() || self.en_passant_capture_square(m).is_some();
+
+                // geometry is right, so only path could have stopped the move
+                match piece.move_kind(m, dest_occupied) {
+                    MoveKind::Sliding | MoveKind::PawnDouble if self.castling(m).is_none() => IllegalReason::PathBlocked,
+                    _ => IllegalReason::PieceCannotMoveThatWay
+                }
+            }
+        };
+
+        Some(reason)
+    }
+
+    /// # Returns kind of given move in current position
+    ///
+    /// unlike `Piece::move_kind` takes board into account, so castling and en passant are recognized
+    /// and impossible moves are `Illegal`
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::piece::MoveKind;
+    ///
+    /// let mut board = Board::from_fen("4k3/3p4/8/4P3/8/8/8/4K2R b K - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.move_kind(Move::from_uci("d7d5").unwrap()), MoveKind::PawnDouble);
+    /// assert!(board.apply_move(Move::from_uci("d7d5").unwrap()).is_ok());
+    ///
+    /// assert_eq!(board.move_kind(Move::from_uci("e5d6").unwrap()), MoveKind::EnPassant);
+    /// assert_eq!(board.move_kind(Move::from_uci("e1g1").unwrap()), MoveKind::Castle);
+    /// assert_eq!(board.move_kind(Move::from_uci("h1h8").unwrap()), MoveKind::Sliding);
+    /// assert_eq!(board.move_kind(Move::from_uci("e5e7").unwrap()), MoveKind::Illegal);
+    /// ```
+    pub fn move_kind(&self, m: Move) -> MoveKind {
+        if self.check_move_possibility(m).is_err() {
+            return MoveKind::Illegal;
+        }
+
+        self.possible_move_kind(m)
+    }
+
+    /// # Returns kind of move already known to be possible
+    fn possible_move_kind(&self, m: Move) -> MoveKind {
+        if self.castling(m).is_some() {
+            MoveKind::Castle
+        } else if self.en_passant_capture_square(m).is_some() {
+            MoveKind::EnPassant
+        } else {
+            self.get_piece(m.start()).unwrap().move_kind(m, self.get_piece(m.end()).is_some())
+        }
+    }
+
+    /// # Returns square of pawn captured by given move if it is en passant attempt
+    fn en_passant_capture_square(&self, m: Move) -> Option<Square> {
+        let en_passant = self.en_passant?;
+        let piece = self.get_piece(m.start())?;
+        let ((sx, sy), (ex, ey)) = m.to_coords();
+
+        // skipped square on 6th rank can only be taken by white and on 3rd rank by black
+        let capturing_color = if ey == 5 { PieceColor::WHITE } else { PieceColor::BLACK };
+
+        if piece.piece_type() == PieceType::Pawn && piece.color() == capturing_color && m.end() == en_passant && sx != ex {
+            Some(Square::new(ex, sy))
+        } else { None }
+    }
+
+    /// # Returns king's and rook's movement if given move is castling attempt
+    ///
+    /// castling is written as king moving two squares towards rook,
+    /// in Chess960 it is written as king moving onto its own rook
+    fn castling(&self, m: Move) -> Option<Castling> {
+        let piece = self.get_piece(m.start())?;
+        let ((sx, sy), (ex, ey)) = m.to_coords();
+
+        if piece.piece_type() != PieceType::King || sy != ey || m.promotion().is_some() {
+            return None;
+        }
+
+        let (king_end_x, rook_x, rook_end_x) = if self.chess960 {
+            let target = self.get_piece(m.end())?;
+
+            if target.piece_type() != PieceType::Rook || target.color() != piece.color() {
+                return None;
+            }
+
+            if ex > sx { (6, ex, 5) } else { (2, ex, 3) }
+        } else if m.to_deltas().0 == 2 {
+            if ex > sx { (ex, 7, ex - 1) } else { (ex, 0, ex + 1) }
+        } else {
+            return None;
+        };
+
+        Some(Castling {
+            king: (m.start(), Square::new(king_end_x, sy)),
+            rook: (Square::new(rook_x, sy), Square::new(rook_end_x, sy))
+        })
+    }
+
+    /// # Checks if castling is possible
+    ///
+    /// neither king nor rook can have moved, squares they pass through have to be empty
+    /// and king can't be in check, pass through or land on attacked square
+    fn check_castling_possibility(&self, castling: Castling) -> Result<(), MoveFailReason> {
+        let (king_start, king_end) = castling.king;
+        let (rook_start, rook_end) = castling.rook;
+
+        let king = self.get_piece(king_start).unwrap();
+        let color = king.color();
+        let home_rank = if color == PieceColor::WHITE { 0 } else { 7 };
+
+        let rook_unmoved = matches!(self.get_piece(rook_start),
+            Some(rook) if rook.piece_type() == PieceType::Rook && rook.color() == color && !rook.has_moved());
+
+        if king.has_moved() || king_start.y() != home_rank || !rook_unmoved {
+            return Err(MoveFailReason::IllegalMove);
+        }
+
+        let files = [king_start.x(), king_end.x(), rook_start.x(), rook_end.x()];
+        let (min_x, max_x) = (*files.iter().min().unwrap(), *files.iter().max().unwrap());
+
+        let path_clear = (min_x..=max_x).map(|x| Square::new(x, home_rank))
+            .all(|square| square == king_start || square == rook_start || self.get_piece(square).is_none());
+
+        if !path_clear {
+            return Err(MoveFailReason::IllegalMove);
+        }
+
+        let (min_x, max_x) = (king_start.x().min(king_end.x()), king_start.x().max(king_end.x()));
+
+        if (min_x..=max_x).any(|x| self.is_square_attacked(Square::new(x, home_rank), !color)) {
+            return Err(MoveFailReason::KingAttacked);
+        }
+
+        // castling rook could have been shielding the king
+        let mut board = self.clone();
+        board.relocate_castling_pieces(castling);
+
+        if board.is_in_check(color) {
+            return Err(MoveFailReason::KingAttacked);
+        }
+
+        Ok(())
+    }
+
+    /// # Moves king and rook to their squares after castling
+    fn relocate_castling_pieces(&mut self, castling: Castling) {
+        let mut king = self.take(castling.king.0.to_index()).unwrap();
+        let mut rook = self.take(castling.rook.0.to_index()).unwrap();
+
+        king.move_piece();
+        rook.move_piece();
+
+        self.put(castling.king.1.to_index(), Some(king));
+        self.put(castling.rook.1.to_index(), Some(rook));
+    }
+
+    // basic board state changers
+    /// # Places piece at given index, keeping bitboards in sync
+    fn put(&mut self, index: usize, piece: Option<Piece>) {
+        self.take(index);
+
+        if let Some(piece) = piece {
+            self.bitboards[bitboard::piece_index(piece.piece_type(), piece.color())] |= bitboard::bit(index);
+        }
+
+        self.pieces[index] = piece;
+    }
+
+    /// # Removes piece from given index, keeping bitboards in sync
+    fn take(&mut self, index: usize) -> Option<Piece> {
+        let piece = self.pieces[index].take();
+        self.invalidate_attacks();
+
+        if let Some(piece) = piece {
+            self.bitboards[bitboard::piece_index(piece.piece_type(), piece.color())] &= !bitboard::bit(index);
+        }
+
+        piece
+    }
+
+    /// # Sets piece at square
+    ///
+    /// should only be used for setting up custom positions
+    /// not for moving pieces during game, legality of resulting position is not checked,
+    /// turn and move history are left untouched, `None` clears the square
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::{Piece, PieceType, PieceColor};
+    ///
+    /// let mut board = Board::new_clear();
+    /// let square = Square::from_uci("e1").unwrap();
+    ///
+    /// board.set(square, Some(Piece::new(PieceType::King, PieceColor::WHITE)));
+    /// assert_eq!(board.get_piece(square).unwrap().piece_type(), PieceType::King);
+    ///
+    /// board.set(square, None);
+    /// assert!(board.get_piece(square).is_none());
+    /// ```
+    pub fn set(&mut self, square: Square, piece: Option<Piece>) {
+        self.put(square.to_index(), piece);
+    }
+
+    /// # Sets side to move
+    ///
+    /// should only be used for setting up custom positions, like `set`,
+    /// legality of resulting position is not checked, fx: side not to move may be left in check
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// board.set_turn(PieceColor::BLACK);
+    /// assert_eq!(board.turn(), PieceColor::BLACK);
+    /// ```
+    pub fn set_turn(&mut self, color: PieceColor) {
+        self.turn = color;
+    }
+
+    /// # Forgets cached attacks, called on every change of piece placement
+    ///
+    /// attacks are computed again on first query, positions which are never queried cost nothing
+    fn invalidate_attacks(&self) {
+        for attacks in self.attacks.iter() {
+            attacks.set(None);
+        }
+    }
+
+    // advanced board state changers
+    /// # Performs move after checking if it is possible
+    ///
+    /// returns captured piece, board is left untouched if move is not possible
+    /// or if it is not moved piece's color turn, call `state` afterwards to find out if game has ended
+    ///
+    /// pawn reaching last rank is promoted to move's promotion piece, which has to be given,
+    /// castling moves the rook as well and en passant removes the passed pawn
+    ///
+    /// board is changed in place and only what `undo` needs to take the move back is recorded,
+    /// so search should apply and undo moves on one board instead of cloning it for every node
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::piece::PieceType;
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// for m in ["e2e4", "d7d5"] {
+    ///     assert!(board.apply_move(Move::from_uci(m).unwrap()).unwrap().is_none());
+    /// }
+    ///
+    /// let captured = board.apply_move(Move::from_uci("e4d5").unwrap()).unwrap();
+    ///
+    /// assert_eq!(captured.unwrap().piece_type(), PieceType::Pawn);
+    /// ```
+    pub fn apply_move(&mut self, m: Move) -> Result<Option<Piece>, MoveFailReason> {
+        self.check_move_possibility(m)?;
+
+        if self.get_piece(m.start()).unwrap().color() != self.turn {
+            return Err(MoveFailReason::WrongTurn);
+        }
+
+        let kind = self.possible_move_kind(m);
+        let castling = if kind == MoveKind::Castle { self.castling(m) } else { None };
+
+        let captured_square = if kind == MoveKind::EnPassant {
+            self.en_passant_capture_square(m).unwrap()
+        } else { m.end() };
+
+        // in Chess960 king moves onto its own rook when castling, which is not a capture
+        let captured = if castling.is_some() { None } else { self.pieces[captured_square.to_index()] };
+
+        let src = m.start().to_index();
+        let dst = m.end().to_index();
+
+        self.history.push(HistoryEntry {
+            m,
+            piece: self.pieces[src].unwrap(),
+            captured: captured.map(|piece| (captured_square, piece)),
+            castling,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            attacks: [self.attacks[0].get(), self.attacks[1].get()],
+            position: self.repetition_key()
+        });
+
+        let pawn_move = self.pieces[src].unwrap().piece_type() == PieceType::Pawn;
+
+        let piece = match castling {
+            Some(castling) => {
+                self.relocate_castling_pieces(castling);
+                self.pieces[castling.king.1.to_index()].unwrap()
+            },
+            None => {
+                self.take(captured_square.to_index());
+
+                let mut piece = self.take(src).unwrap();
+                piece.move_piece();
+
+                // validation makes sure promotion piece is given exactly when pawn reaches last rank
+                if let Some(promotion) = m.promotion() {
+                    piece.promote(promotion);
+                }
+
+                self.put(dst, Some(piece));
+                piece
+            }
+        };
+
+        let ((sx, sy), (_, ey)) = m.to_coords();
+
+        self.en_passant = if kind == MoveKind::PawnDouble {
+            Some(Square::new(sx, (sy + ey) / 2))
+        } else { None };
+
+        self.halfmove_clock = if pawn_move || captured.is_some() {
+            0
+        } else { self.halfmove_clock.saturating_add(1) };
+
+        if piece.color() == PieceColor::BLACK {
+            self.fullmove_number = self.fullmove_number.saturating_add(1);
+        }
+
+        self.turn = !piece.color();
+
+        Ok(captured)
+    }
+
+    /// # Takes back the last move
+    ///
+    /// returns the move taken back or None if there are no moves to take back
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+    /// assert_eq!(board.undo(), Some(Move::from_uci("e2e4").unwrap()));
+    /// assert_eq!(board.undo(), None);
+    ///
+    /// assert_eq!(board.to_fen(), Board::new().to_fen());
+    /// ```
+    pub fn undo(&mut self) -> Option<Move> {
+        let entry = self.history.pop()?;
+        let m = entry.m;
+
+        self.turn = entry.piece.color();
+
+        if let Some(castling) = entry.castling {
+            self.take(castling.king.1.to_index());
+
+            let mut rook = self.take(castling.rook.1.to_index());
+            rook.as_mut().unwrap().reset_moved();
+
+            self.put(castling.rook.0.to_index(), rook);
+            self.put(castling.king.0.to_index(), Some(entry.piece));
+        } else {
+            self.put(m.end().to_index(), None);
+            self.put(m.start().to_index(), Some(entry.piece));
+
+            if let Some((square, piece)) = entry.captured {
+                self.put(square.to_index(), Some(piece));
+            }
+        }
+
+        self.en_passant = entry.en_passant;
+        self.halfmove_clock = entry.halfmove_clock;
+        self.fullmove_number = entry.fullmove_number;
+
+        // placement is back to what it was before the move, so are its attacks
+        for (attacks, saved) in self.attacks.iter().zip(entry.attacks) {
+            attacks.set(saved);
+        }
+
+        Some(m)
+    }
+
+    /// # Returns pieces captured by given color in moves recorded in history, in order of capture
+    pub(crate) fn captured_by(&self, color: PieceColor) -> impl Iterator<Item = Piece> + '_ {
+        self.history.iter()
+            .filter(move |entry| entry.piece.color() == color)
+            .filter_map(|entry| entry.captured.map(|(_, piece)| piece))
+    }
+
+    /// # Passes the turn to the opponent without moving any piece
+    ///
+    /// meant for null move pruning in search, en passant square is cleared,
+    /// must not be used when side to move is in check,
+    /// has to be taken back with `undo_null_move` before taking back any regular move
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// board.make_null_move();
+    /// assert_eq!(board.turn(), PieceColor::BLACK);
+    ///
+    /// board.undo_null_move();
+    /// assert_eq!(board.turn(), PieceColor::WHITE);
+    /// ```
+    pub fn make_null_move(&mut self) {
+        debug_assert!(!self.is_in_check(self.turn), "null move made while in check");
+
+        self.null_moves.push(self.en_passant.take());
+        self.turn = !self.turn;
+    }
+
+    /// # Takes back the last null move
+    ///
+    /// restores side to move and en passant square, does nothing if there is no null move to take back
+    pub fn undo_null_move(&mut self) {
+        if let Some(en_passant) = self.null_moves.pop() {
+            self.en_passant = en_passant;
+            self.turn = !self.turn;
+        }
+    }
+
+    /// # Performs move after checking if it is possible
+    ///
+    /// same as `apply_move` but returns state of the game after move
+    ///
+    /// ```
+    /// # use chess_api::movement::{Move, Square};
+    /// # use chess_api::piece::{PieceType, PieceColor};
+    /// # use chess_api::board::Board;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// board.perform_move(Move::new(Square::new(1, 1), Square::new(1, 3)));
+    ///
+    /// assert_eq!(board.get_piece(Square::new(1, 3)).unwrap().piece_type(), PieceType::Pawn);
+    /// assert_eq!(board.get_piece(Square::new(1, 3)).unwrap().color(), PieceColor::WHITE);
+    /// assert!(board.get_piece(Square::new(1, 1)).is_none());
+    /// ```
+    pub fn perform_move(&mut self, m: Move) -> Result<PostMoveState, MoveFailReason> {
+        self.apply_move(m)?;
+
+        let next_color = self.turn;
+
+        let king_attacked = self.is_king_attacked(next_color);
+        let has_moves = self.all_possible_moves(Some(next_color)).next().is_some();
+
+        if king_attacked && has_moves {
+            Ok(PostMoveState::Check)
+        } else if king_attacked {
+            Ok(PostMoveState::Checkmate)
+        } else if has_moves {
+            Ok(PostMoveState::Normal)
+        } else {
+            Ok(PostMoveState::Stelemate)
+        }
+    }
+
+    /// # Returns move written in standard algebraic notation
+    ///
+    /// move is expected to be possible in current position,
+    /// check and checkmate suffixes are only added for possible moves
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.move_to_san(Move::from_uci("g1f3").unwrap()), "Nf3");
+    /// assert_eq!(board.move_to_san(Move::from_uci("e2e4").unwrap()), "e4");
+    /// ```
+    pub fn move_to_san(&self, m: Move) -> String {
+        let piece = match self.get_piece(m.start()) {
+            Some(piece) => piece,
+            None => return m.to_uci()
+        };
+
+        let mut san = if let Some(castling) = self.castling(m) {
+            String::from(if castling.rook.0.x() > castling.king.0.x() { "O-O" } else { "O-O-O" })
+        } else {
+            let capture = self.get_piece(m.end()).is_some() || self.en_passant_capture_square(m).is_some();
+            let (file, rank) = (m.start().to_uci().chars().next().unwrap(), m.start().to_coords().1 + 1);
+            let mut san = String::new();
+
+            if piece.piece_type() == PieceType::Pawn {
+                if capture {
+                    san.push(file);
+                }
+            } else {
+                san.push(piece.get_character().to_ascii_uppercase());
+
+                let others: Vec<Square> = self.all_possible_moves(Some(piece.color()))
+                    .filter(|other| other.end() == m.end() && other.start() != m.start())
+                    .filter(|other| self.get_piece(other.start()).unwrap().piece_type() == piece.piece_type())
+                    .map(|other| other.start())
+                    .collect();
+
+                // per PGN spec: file if it is unique, otherwise rank if it is unique, otherwise both
+                if !others.is_empty() {
+                    let (x, y) = m.start().to_coords();
+
+                    if others.iter().all(|other| other.to_coords().0 != x) {
+                        san.push(file);
+                    } else if others.iter().all(|other| other.to_coords().1 != y) {
+                        san.push_str(&rank.to_string());
+                    } else {
+                        san.push_str(&m.start().to_uci());
+                    }
+                }
+            }
+
+            if capture {
+                san.push('x');
+            }
+
+            san.push_str(&m.end().to_uci());
+
+            if piece.piece_type() == PieceType::Pawn && is_last_rank(m.end(), piece.color()) {
+                san.push('=');
+                san.push(Piece::new(m.promotion().unwrap_or(PieceType::Queen), PieceColor::WHITE).get_character());
+            }
+
+            san
+        };
+
+        let mut board = self.clone();
+
+        if board.apply_move(m).is_ok() && board.is_in_check(board.turn) {
+            san.push(if board.all_possible_moves(Some(board.turn)).next().is_some() { '+' } else { '#' });
+        }
+
+        san
+    }
+
+    /// # Parses move written in standard algebraic notation
+    ///
+    /// move is resolved against legal moves of the side to move,
+    /// check, checkmate and annotation suffixes are ignored
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::board::{Board, SanError};
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.san_to_move("Nf3"), Ok(Move::from_uci("g1f3").unwrap()));
+    /// assert_eq!(board.san_to_move("e4"), Ok(Move::from_uci("e2e4").unwrap()));
+    /// assert_eq!(board.san_to_move("e5"), Err(SanError::IllegalMove));
+    /// ```
+    pub fn san_to_move(&self, san: &str) -> Result<Move, SanError> {
+        let san = san.trim_end_matches(['+', '#', '!', '?']);
+
+        if san == "O-O" || san == "0-0" || san == "O-O-O" || san == "0-0-0" {
+            let king = self.king_square(self.turn).ok_or(SanError::IllegalMove)?;
+            let kingside = san.len() == 3;
+
+            return self.all_possible_moves_from_square(king)
+                .find(|m| self.castling(*m).is_some_and(|castling| (castling.rook.0.x() > king.x()) == kingside))
+                .ok_or(SanError::IllegalMove);
+        }
+
+        if !san.is_ascii() {
+            return Err(SanError::InvalidFormat);
+        }
+
+        let (piece_type, rest) = match san.chars().next() {
+            Some('N') => (PieceType::Knight, &san[1..]),
+            Some('B') => (PieceType::Bishop, &san[1..]),
+            Some('R') => (PieceType::Rook, &san[1..]),
+            Some('Q') => (PieceType::Queen, &san[1..]),
+            Some('K') => (PieceType::King, &san[1..]),
+            _ => (PieceType::Pawn, san)
+        };
+
+        let (rest, promotion) = match rest.char_indices().last() {
+            Some((i, c)) if c.is_ascii_uppercase() => {
+                let promotion = match c {
+                    'N' => PieceType::Knight,
+                    'B' => PieceType::Bishop,
+                    'R' => PieceType::Rook,
+                    'Q' => PieceType::Queen,
+                    _ => return Err(SanError::InvalidFormat)
+                };
+
+                (rest[..i].trim_end_matches('='), Some(promotion))
+            },
+            _ => (rest, None)
+        };
+
+        if rest.len() < 2 {
+            return Err(SanError::InvalidFormat);
+        }
+
+        let (from, end) = rest.split_at(rest.len() - 2);
+        let end = Square::from_uci(end).map_err(|_| SanError::InvalidFormat)?;
+        let from = from.trim_end_matches('x');
+
+        let mut file = None;
+        let mut rank = None;
+
+        for c in from.chars() {
+            match c {
+                'a'..='h' if file.is_none() && rank.is_none() => file = Some(c as u8 - b'a'),
+                '1'..='8' if rank.is_none() => rank = Some(c as u8 - b'1'),
+                _ => return Err(SanError::InvalidFormat)
+            }
+        }
+
+        let mut candidates = self.all_possible_moves(Some(self.turn)).filter(|m| {
+            let (x, y) = m.start().to_coords();
+            let piece = self.get_piece(m.start()).unwrap();
+
+            m.end() == end && piece.piece_type() == piece_type && m.promotion() == promotion
+                && file.is_none_or(|file| file == x) && rank.is_none_or(|rank| rank == y)
+        });
+
+        let m = candidates.next().ok_or(SanError::IllegalMove)?;
+
+        if candidates.next().is_some() {
+            return Err(SanError::AmbiguousMove);
+        }
+
+        Ok(m)
+    }
+
+    /// # Parses move written in long algebraic notation, fx: Ng1-f3, e2-e4, Qd1xd7+, e7-e8=Q
+    ///
+    /// piece letter is optional and has to match piece on start square when given,
+    /// start and end may be separated by `-` or `x` which is not checked against the move,
+    /// promotion piece may follow end square with or without `=`, suffixes are ignored like in SAN
+    ///
+    /// ```
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::board::{Board, LanError};
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.parse_long_algebraic("Ng1-f3"), Ok(Move::from_uci("g1f3").unwrap()));
+    /// assert_eq!(board.parse_long_algebraic("e2e4"), Ok(Move::from_uci("e2e4").unwrap()));
+    /// assert_eq!(board.parse_long_algebraic("Bg1-f3"), Err(LanError::IllegalMove));
+    /// ```
+    pub fn parse_long_algebraic(&self, lan: &str) -> Result<Move, LanError> {
+        let lan = lan.trim_end_matches(['+', '#', '!', '?']);
+
+        if lan == "O-O" || lan == "0-0" || lan == "O-O-O" || lan == "0-0-0" {
+            return self.san_to_move(lan).map_err(|_| LanError::IllegalMove);
+        }
+
+        if !lan.is_ascii() {
+            return Err(LanError::InvalidFormat);
+        }
+
+        let (piece_type, rest) = match lan.chars().next() {
+            Some('N') => (Some(PieceType::Knight), &lan[1..]),
+            Some('B') => (Some(PieceType::Bishop), &lan[1..]),
+            Some('R') => (Some(PieceType::Rook), &lan[1..]),
+            Some('Q') => (Some(PieceType::Queen), &lan[1..]),
+            Some('K') => (Some(PieceType::King), &lan[1..]),
+            _ => (None, lan)
+        };
+
+        if rest.len() < 4 {
+            return Err(LanError::InvalidFormat);
+        }
+
+        let start = Square::from_uci(&rest[..2]).map_err(|_| LanError::InvalidFormat)?;
+        let rest = rest[2..].strip_prefix(['-', 'x']).unwrap_or(&rest[2..]);
+
+        if rest.len() < 2 {
+            return Err(LanError::InvalidFormat);
+        }
+
+        let end = Square::from_uci(&rest[..2]).map_err(|_| LanError::InvalidFormat)?;
+
+        let promotion = match rest[2..].strip_prefix('=').unwrap_or(&rest[2..]) {
+            "" if !rest[2..].starts_with('=') => None,
+            "N" | "n" => Some(PieceType::Knight),
+            "B" | "b" => Some(PieceType::Bishop),
+            "R" | "r" => Some(PieceType::Rook),
+            "Q" | "q" => Some(PieceType::Queen),
+            _ => return Err(LanError::InvalidFormat)
+        };
+
+        let piece = self.get_piece(start).ok_or(LanError::IllegalMove)?;
+
+        if piece.color() != self.turn || piece_type.is_some_and(|piece_type| piece_type != piece.piece_type()) {
+            return Err(LanError::IllegalMove);
+        }
+
+        self.all_possible_moves_from_square(start)
+            .find(|m| m.end() == end && m.promotion() == promotion)
+            .ok_or(LanError::IllegalMove)
+    }
+
+    // iterators
+    fn squares_after_move(&self, sm: Option<Move>) -> impl Iterator<Item = (Square, Option<&Piece>)> {
+        (0..64).map(|i| Square::from_index(i).unwrap()).map(move |square| (square, self.get_piece_after_move(square, sm)))
+    }
+
+    /// # Returns iterator over every square on the board
+    ///
+    /// squares are yielded in rank-major order: a1, b1, ..., h1, a2, ..., h8
+    pub fn squares(&self) -> impl Iterator<Item = (Square, Option<&Piece>)> {
+        self.squares_after_move(None)
+    }
+
+    /// # Returns iterator over every square on the board with its piece
+    ///
+    /// same as `squares`, squares are yielded in rank-major order: a1, b1, ..., h1, a2, ..., h8
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    ///
+    /// let board = Board::new();
+    /// let squares: Vec<Square> = board.iter().map(|(square, _)| square).collect();
+    ///
+    /// assert_eq!(squares.len(), 64);
+    /// assert_eq!(squares[0].to_uci(), "a1");
+    /// assert_eq!(squares[7].to_uci(), "h1");
+    /// assert_eq!(squares[8].to_uci(), "a2");
+    /// assert_eq!(board.iter().filter(|(_, piece)| piece.is_some()).count(), 32);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (Square, Option<&Piece>)> {
+        self.squares()
+    }
+
+    fn pieces_after_move(&self, color: Option<PieceColor>, sm: Option<Move>) -> impl Iterator<Item = (Square, &Piece)> {
+        // simulated move can only bring a piece onto its end square
+        let candidates = self.occupancy(color) | sm.map_or(0, |sm| bitboard::bit(sm.end().to_index()));
+
+        bitboard::indices(candidates).filter_map(move |i| match self.get_piece_at_index_after_move(i, sm) {
+            Some(piece) if color.is_none_or(|color| piece.color() == color) => Some((Square::from_index(i).unwrap(), piece)),
+            _ => None
+        })
+    }
+
+    /// # Returns iterator over every piece on the board, or only pieces of given color
+    ///
+    /// reads occupied squares directly without going through every square's lookup,
+    /// pieces are yielded in rank-major order
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::PieceColor;
+    /// 
+    /// # let board = Board::new();
+    ///
+    /// for (square, piece) in board.pieces(None) {
+    ///     println!("{:?} => {:?}", square, piece);
+    /// }
+    ///
+    /// assert_eq!(board.pieces(Some(PieceColor::WHITE)).count(), 16);
+    /// ```
+    pub fn pieces(&self, color: Option<PieceColor>) -> impl Iterator<Item = (Square, &Piece)> {
+        bitboard::indices(self.occupancy(color)).map(|i| (Square::from_index(i).unwrap(), self.pieces[i].as_ref().unwrap()))
+    }
+
+    // advanced board state getters
+    /// # Returns every square attacked by given color
+    ///
+    /// squares occupied by given color's own pieces are not included, same as in `is_square_attacked`
+    fn compute_attacks(&self, color: PieceColor) -> u64 {
+        #[cfg(feature = "attack-stats")]
+        ATTACK_COMPUTATIONS.fetch_add(1, Ordering::Relaxed);
+
+        let occupancy = self.occupancy(None);
+
+        let attacks = bitboard::indices(self.occupancy(Some(color))).fold(0, |attacks, i| {
+            let piece_type = self.pieces[i].unwrap().piece_type();
+
+            attacks | match piece_type {
+                PieceType::Pawn => bitboard::pawn_attacks(color, i),
+                PieceType::Knight | PieceType::King => bitboard::targets(piece_type, color, i),
+                _ => bitboard::indices(bitboard::targets(piece_type, color, i))
+                    .filter(|end| bitboard::between(i, *end) & occupancy == 0)
+                    .fold(0, |targets, end| targets | bitboard::bit(end))
+            }
+        });
+
+        attacks & !self.occupancy(Some(color))
+    }
+
+    /// # Returns every square attacked by given color, computed on first query after placement changed
+    fn attacks(&self, color: PieceColor) -> u64 {
+        #[cfg(feature = "attack-stats")]
+        ATTACK_QUERIES.fetch_add(1, Ordering::Relaxed);
+
+        let cached = &self.attacks[color as usize];
+
+        cached.get().unwrap_or_else(|| {
+            let attacks = self.compute_attacks(color);
+            cached.set(Some(attacks));
+            attacks
+        })
+    }
+
+    /// # Returns numbers of attack queries and attack computations made by all boards since last reset
+    ///
+    /// without the cache every query would compute attacks, so `queries` is the cost of uncached attacks
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// Board::reset_attack_stats();
+    /// Board::new().perft(2);
+    ///
+    /// let stats = Board::attack_stats();
+    /// assert!(stats.computations < stats.queries);
+    /// ```
+    #[cfg(feature = "attack-stats")]
+    pub fn attack_stats() -> AttackStats {
+        AttackStats {
+            queries: ATTACK_QUERIES.load(Ordering::Relaxed),
+            computations: ATTACK_COMPUTATIONS.load(Ordering::Relaxed)
+        }
+    }
+
+    #[cfg(feature = "attack-stats")]
+    pub fn reset_attack_stats() {
+        ATTACK_QUERIES.store(0, Ordering::Relaxed);
+        ATTACK_COMPUTATIONS.store(0, Ordering::Relaxed);
+    }
+
+    /// # Returns true if given square is attacked by given player after simulating move
+    fn is_square_attacked_after_move(&self, square: Square, color: PieceColor, sm: Option<Move>) -> bool {
+        if sm.is_none() {
+            return self.attacks(color) & bitboard::bit(square.to_index()) != 0;
+        }
+
+        let target = self.get_piece_after_move(square, sm);
+
+        self.pieces_after_move(Some(color), sm)
+            .filter(|(start, piece)| *start != square && bitboard::targets(piece.piece_type(), piece.color(), start.to_index()) & bitboard::bit(square.to_index()) != 0)
+            .any(|(start, piece)| match piece.piece_type() {
+                // table hit is enough for pieces which can't be blocked
+                PieceType::Knight | PieceType::King => target.is_none_or(|target| target.color() != color),
+                _ => self.attacks_after_move(Move::new(start, square), sm)
+            })
+    }
+
+    /// # Returns true if piece at move's start could capture on move's end after simulating move
+    ///
+    /// differs from move possibility only for pawns, which attack diagonally even if destination is empty
+    fn attacks_after_move(&self, m: Move, sm: Option<Move>) -> bool {
+        match self.get_piece_after_move(m.start(), sm) {
+            Some(piece) if piece.piece_type() == PieceType::Pawn => {
+                piece.can_move_to(m, true).0 && self.get_piece_after_move(m.end(), sm).is_none_or(|target| target.color() != piece.color())
+            },
+            Some(_) => self.check_pseudo_move_possibility_after_move(m, sm).is_ok(),
+            None => false
+        }
+    }
+
+    /// # Returns true if given square is attacked by given player
+    ///
+    /// pawns attack diagonal squares even when they are empty,
+    /// will return false if attacked only by allied piece
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// board.set(Square::new(3, 1), None); // d2 ( in front of white's queen )
+    /// 
+    /// assert_eq!(board.is_square_attacked(Square::new(3, 6), PieceColor::WHITE), true); // d7
+    /// assert_eq!(board.is_square_attacked(Square::new(3, 6), PieceColor::BLACK), false);
+    /// assert_eq!(board.is_square_attacked(Square::new(3, 1), PieceColor::WHITE), true);
+    /// assert_eq!(board.is_square_attacked(Square::new(3, 1), PieceColor::BLACK), false);
+    /// ```
+    pub fn is_square_attacked(&self, square: Square, by: PieceColor) -> bool {
+        self.is_square_attacked_after_move(square, by, None)
+    }
+
+    /// # Returns squares of every piece of given color attacking given square
+    ///
+    /// pawns attack diagonally even if square is empty, sliding pieces have to have clear path
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let board = Board::new();
+    /// let attackers = board.attackers_of(Square::from_uci("f3").unwrap(), PieceColor::WHITE);
+    ///
+    /// assert_eq!(attackers, vec![Square::from_uci("g1").unwrap(), Square::from_uci("e2").unwrap(), Square::from_uci("g2").unwrap()]);
+    /// ```
+    pub fn attackers_of(&self, square: Square, by: PieceColor) -> Vec<Square> {
+        self.pieces(Some(by))
+            .filter(|(start, _)| *start != square && self.attacks_after_move(Move::new(*start, square), None))
+            .map(|(start, _)| start)
+            .collect()
+    }
+
+    /// # Returns square of king of given color, or None if there is no such king
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// assert_eq!(Board::new().king_square(PieceColor::BLACK), Square::from_uci("e8").ok());
+    /// assert_eq!(Board::new_clear().king_square(PieceColor::WHITE), None);
+    /// ```
+    pub fn king_square(&self, color: PieceColor) -> Option<Square> {
+        bitboard::indices(self.bitboards[bitboard::piece_index(PieceType::King, color)]).next().map(|i| Square::from_index(i).unwrap())
+    }
+
+    /// # Returns square of king of given color after simulating move
+    fn king_square_after_move(&self, color: PieceColor, sm: Option<Move>) -> Option<Square> {
+        let king = self.king_square(color)?;
+
+        match sm {
+            Some(sm) if sm.start() == king => Some(sm.end()),
+            Some(sm) if sm.end() == king => None,
+            _ => Some(king)
+        }
+    }
+
+    /// # If king is attacked returns true
+    fn is_king_attacked_after_move(&self, color: PieceColor, sm: Option<Move>) -> bool {
+        // king not in check can only be exposed by its own move, en passant or a piece leaving king's lines
+        if let (Some(sm), Some(king)) = (sm, self.king_square(color)) {
+            let safe = self.attacks(!color) & bitboard::bit(king.to_index()) == 0
+                && sm.start() != king
+                && self.get_piece(sm.start()).is_some_and(|piece| piece.color() == color)
+                && self.en_passant_capture_square(sm).is_none()
+                && bitboard::targets(PieceType::Queen, color, king.to_index()) & bitboard::bit(sm.start().to_index()) == 0;
+
+            if safe {
+                return false;
+            }
+        }
+
+        if let Some(square) = self.king_square_after_move(color, sm) {
+            self.is_square_attacked_after_move(square, !color, sm)
+        } else { false }
+    }
+
+    /// # If king is attacked returns true
+    ///
+    /// same as `is_in_check`
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::{Move, Square};
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// assert!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok()); // e4
+    /// assert!(board.perform_move(Move::new(Square::new(5, 6), Square::new(5, 4))).is_ok()); // f6
+    /// assert!(board.perform_move(Move::new(Square::new(3, 0), Square::new(7, 4))).is_ok()); // Qh5
+    ///
+    /// assert_eq!(board.is_king_attacked(PieceColor::WHITE), false);
+    /// assert_eq!(board.is_king_attacked(PieceColor::BLACK), true);
+    /// ```
+    pub fn is_king_attacked(&self, color: PieceColor) -> bool {
+        self.is_in_check(color)
+    }
+
+    /// # Returns true if king of given color is in check
+    ///
+    /// king is in check when any enemy piece could capture it, even if that piece is pinned,
+    /// returns false when there is no king of given color on the board
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.is_in_check(PieceColor::WHITE), true);
+    /// assert_eq!(board.is_in_check(PieceColor::BLACK), false);
+    /// ```
+    pub fn is_in_check(&self, color: PieceColor) -> bool {
+        self.is_king_attacked_after_move(color, None)
+    }
+
+    /// # Returns true if mover's king would be in check after given move
+    ///
+    /// board is not changed, move is only simulated, so it doesn't have to be possible,
+    /// returns false if there is no piece on move's start
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Move;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/4r3/4K3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.in_check_after(Move::from_uci("e1f1").unwrap()), false);
+    /// assert_eq!(board.in_check_after(Move::from_uci("e1d2").unwrap()), true);
+    /// ```
+    pub fn in_check_after(&self, m: Move) -> bool {
+        let color = match self.get_piece(m.start()) {
+            Some(piece) => piece.color(),
+            None => return false
+        };
+
+        match self.castling(m) {
+            Some(castling) => {
+                let mut board = self.clone();
+                board.relocate_castling_pieces(castling);
+                board.is_in_check(color)
+            },
+            None => self.is_king_attacked_after_move(color, Some(m))
+        }
+    }
+
+    /// # Returns pieces of given color pinned to their king
+    ///
+    /// each pinned piece's square is paired with square of the piece pinning it,
+    /// pinned piece can still move along the pin line
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Square;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/8/8/8/8/r2QK3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.pinned_pieces(PieceColor::WHITE), vec![(Square::new(3, 0), Square::new(0, 0))]);
+    /// assert!(board.pinned_pieces(PieceColor::BLACK).is_empty());
+    /// ```
+    pub fn pinned_pieces(&self, color: PieceColor) -> Vec<(Square, Square)> {
+        let king = match self.king_square(color) {
+            Some(square) => square,
+            None => return Vec::new()
+        };
+
+        self.pieces(Some(!color))
+            .filter(|(square, piece)| {
+                let m = Move::new(*square, king);
+                matches!(piece.piece_type(), PieceType::Rook | PieceType::Bishop | PieceType::Queen)
+                    && (m.is_straight() || m.is_diagonal()) && piece.can_move_to(m, true).0
+            })
+            .filter_map(|(attacker, _)| {
+                let mut blockers = Square::between(attacker, king).filter(|square| self.get_piece(*square).is_some());
+
+                match (blockers.next(), blockers.next()) {
+                    (Some(pinned), None) if self.get_piece(pinned).unwrap().color() == color => Some((pinned, attacker)),
+                    _ => None
+                }
+            })
+            .collect()
+    }
+
+    /// # Returns iterator for every possible move from given square
+    ///
+    /// pawn reaching last rank yields separate move for every promotion choice,
+    /// move order is not defined and may change in future
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::{Move, Square};
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let mut board = Board::new();
+    ///
+    /// assert!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok()); // e4
+    /// 
     /// assert_eq!(board.all_possible_moves_from_square(Square::new(3, 0)).count(), 4); // queen
     /// assert_eq!(board.all_possible_moves_from_square(Square::new(4, 3)).count(), 1);
     /// assert_eq!(board.all_possible_moves_from_square(Square::new(3, 1)).count(), 2);
     /// ```
-
     pub fn all_possible_moves_from_square<'a>(&'a self, start: Square) -> impl Iterator<Item = Move> + 'a {
-        // todo optimize, piece should give subset of board's squares to test for move possibility
-        // fx: for pawn we are testing all 64 destination squares but we should only test 4
-        self.squares()
-            .filter_map(move |(end, _)| if end == start { None } else { Some(end) })
-            .map(move |end| Move::new(start, end))
+        self.possible_moves_from_square_to(start, !0)
+    }
+
+    /// # Returns iterator for every possible move from given square ending on one of given squares
+    fn possible_moves_from_square_to<'a>(&'a self, start: Square, mask: u64) -> impl Iterator<Item = Move> + 'a {
+        let targets = match self.get_piece(start) {
+            // castling may put king on any square of its rank, fx: onto its own rook in Chess960
+            Some(piece) if piece.piece_type() == PieceType::King => bitboard::targets(PieceType::King, piece.color(), start.to_index()) | 0xff << (8 * start.y()),
+            Some(piece) => bitboard::targets(piece.piece_type(), piece.color(), start.to_index()),
+            None => 0
+        };
+
+        let pawn = self.get_piece(start).filter(|piece| piece.piece_type() == PieceType::Pawn);
+
+        bitboard::indices(targets & mask & !bitboard::bit(start.to_index()))
+            .map(move |end| Move::new(start, Square::from_index(end).unwrap()))
+            .flat_map(move |m| {
+                let promotes = pawn.is_some_and(|pawn| is_last_rank(m.end(), pawn.color()));
+                let moves = [PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight]
+                    .map(|promotion| if promotes { Move::with_promotion(m.start(), m.end(), promotion) } else { m });
+
+                moves.into_iter().take(if promotes { 4 } else { 1 })
+            })
             .filter(|m| self.check_move_possibility(*m).is_ok())
     }
 
@@ -355,4 +2509,387 @@ impl Board {
     pub fn all_possible_moves<'a>(&'a self, color: Option<PieceColor>) -> impl Iterator<Item = Move> +'a {
         self.pieces(color).flat_map(|(square, _)| self.all_possible_moves_from_square(square))
     }
+
+    /// # Returns every legal move of given color
+    ///
+    /// moves leaving mover's king in check are not included,
+    /// move order is not defined and may change in future
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// # let board = Board::new();
+    ///
+    /// assert_eq!(board.legal_moves(PieceColor::WHITE).len(), 20);
+    /// assert_eq!(board.legal_moves(PieceColor::BLACK).len(), 20);
+    /// ```
+    pub fn legal_moves(&self, color: PieceColor) -> Vec<Move> {
+        self.legal_moves_iter(color).collect()
+    }
+
+    /// # Returns lazy iterator over every legal move of given color
+    ///
+    /// moves are generated only as they are requested, so search can stop early without paying
+    /// for the whole list, captures ( including en passant ) are yielded first and quiet moves after them,
+    /// order within each group is not defined and may change in future
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.legal_moves_iter(PieceColor::WHITE).next(), Some(Move::from_uci("e4d5").unwrap()));
+    /// assert_eq!(board.legal_moves_iter(PieceColor::WHITE).count(), 7);
+    /// ```
+    pub fn legal_moves_iter<'a>(&'a self, color: PieceColor) -> impl Iterator<Item = Move> + 'a {
+        let enemies = self.occupancy(Some(!color));
+        let en_passant = self.en_passant.map_or(0, |square| bitboard::bit(square.to_index()));
+
+        // en passant square is only a capture for pawns, other pieces moving there make quiet move
+        let capture_mask = move |piece: &Piece| match piece.piece_type() {
+            PieceType::Pawn => enemies | en_passant,
+            _ => enemies
+        };
+
+        let captures = self.pieces(Some(color)).flat_map(move |(square, piece)| self.possible_moves_from_square_to(square, capture_mask(piece)));
+        let quiet = self.pieces(Some(color)).flat_map(move |(square, piece)| self.possible_moves_from_square_to(square, !capture_mask(piece)));
+
+        captures.chain(quiet)
+    }
+
+    /// # Returns number of legal move sequences of given depth
+    ///
+    /// used to validate move generation against known node counts
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::new();
+    ///
+    /// assert_eq!(board.perft(0), 1);
+    /// assert_eq!(board.perft(1), 20);
+    /// ```
+    pub fn perft(&self, depth: u8) -> u64 {
+        self.clone().perft_in_place(depth)
+    }
+
+    /// # Counts move sequences by applying and taking back moves on the same board
+    fn perft_in_place(&mut self, depth: u8) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.legal_moves(self.turn);
+
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        moves.into_iter().map(|m| {
+            self.apply_move(m).expect("generated move should be legal");
+            let nodes = self.perft_in_place(depth - 1);
+            self.undo();
+            nodes
+        }).sum()
+    }
+
+    /// # Sorts moves so captures come first, most valuable victim and then least valuable attacker first
+    ///
+    /// moves which capture nothing keep their relative order after all captures
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Move;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/3q4/2P1p3/8/8/3QK3 w - - 0 1").unwrap();
+    /// let mut moves = ["e1e2", "d1d5", "c4d5"].map(|m| Move::from_uci(m).unwrap());
+    ///
+    /// board.order_moves(&mut moves);
+    ///
+    /// assert_eq!(moves.map(|m| m.to_uci()), ["c4d5", "d1d5", "e1e2"]);
+    /// ```
+    pub fn order_moves(&self, moves: &mut [Move]) {
+        moves.sort_by_key(|m| {
+            let victim = match self.get_piece(m.end()) {
+                Some(piece) => Some(piece.piece_type()),
+                None => self.en_passant_capture_square(*m).map(|_| PieceType::Pawn)
+            };
+
+            let attacker = self.get_piece(m.start()).map_or(0, |piece| piece.piece_type().value());
+
+            core::cmp::Reverse(victim.map(|victim| (victim.value(), -attacker)))
+        });
+    }
+
+    /// # Returns best move for side to move found by searching given number of plies
+    ///
+    /// uses negamax with alpha-beta pruning and material balance as evaluation,
+    /// returns None if side to move has no legal moves
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Move;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.best_move(2), Some(Move::from_uci("d1d5").unwrap()));
+    /// ```
+    pub fn best_move(&self, depth: u8) -> Option<Move> {
+        self.search_root(depth, || false).flatten()
+    }
+
+    /// # Returns best move found by iterative deepening within given time
+    ///
+    /// searches with increasing depth and returns best move of the deepest completed search,
+    /// time is checked between top-level moves so a single move's search may overrun the budget slightly,
+    /// at least one ply is always searched, returns None if side to move has no legal moves
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::movement::Move;
+    /// # use std::time::Duration;
+    ///
+    /// let board = Board::from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1").unwrap();
+    ///
+    /// assert_eq!(board.best_move_timed(Duration::from_millis(100)), Some(Move::from_uci("d1d5").unwrap()));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn best_move_timed(&self, max: std::time::Duration) -> Option<Move> {
+        let start = std::time::Instant::now();
+        let mut best = self.search_root(1, || false).flatten()?;
+
+        for depth in 2..=u8::MAX {
+            match self.search_root(depth, || start.elapsed() >= max) {
+                Some(Some(m)) => best = m,
+                _ => break
+            }
+        }
+
+        Some(best)
+    }
+
+    /// # Searches every legal move to given depth
+    ///
+    /// returns None if search was stopped, `stop` is checked before every top-level move
+    fn search_root(&self, depth: u8, stop: impl Fn() -> bool) -> Option<Option<Move>> {
+        let mut board = self.clone();
+        let mut best = None;
+        let mut alpha = -SCORE_INFINITY;
+
+        let mut moves = self.legal_moves(self.turn);
+        self.order_moves(&mut moves);
+
+        for m in moves {
+            if stop() {
+                return None;
+            }
+
+            board.apply_move(m).expect("generated move should be legal");
+            let score = -board.negamax(depth.saturating_sub(1), -SCORE_INFINITY, -alpha);
+            board.undo();
+
+            if best.is_none() || score > alpha {
+                alpha = score;
+                best = Some(m);
+            }
+        }
+
+        Some(best)
+    }
+
+    /// # Returns score of position from side to move's perspective
+    fn negamax(&mut self, depth: u8, mut alpha: i32, beta: i32) -> i32 {
+        if depth == 0 {
+            return match self.turn {
+                PieceColor::WHITE => self.material_balance(),
+                PieceColor::BLACK => -self.material_balance()
+            };
+        }
+
+        let mut moves = self.legal_moves(self.turn);
+
+        if moves.is_empty() {
+            // faster mates are preferred, remaining depth is higher when mate is closer
+            return if self.is_in_check(self.turn) { -MATE_SCORE - depth as i32 } else { 0 };
+        }
+
+        self.order_moves(&mut moves);
+
+        for m in moves {
+            self.apply_move(m).expect("generated move should be legal");
+            let score = -self.negamax(depth - 1, -beta, -alpha);
+            self.undo();
+
+            if score >= beta {
+                return beta;
+            }
+
+            alpha = alpha.max(score);
+        }
+
+        alpha
+    }
+}
+
+/// # Builder for setting up custom positions
+///
+/// pieces are placed on empty board, kings and rooks are treated as moved
+/// unless castling right is granted with `castling`, pawns away from their starting rank are treated as moved
+///
+/// ```
+/// # use chess_api::board::BoardBuilder;
+/// # use chess_api::movement::Square;
+/// # use chess_api::piece::{PieceType, PieceColor};
+///
+/// let board = BoardBuilder::new()
+///     .piece(Square::from_uci("e1").unwrap(), PieceType::King, PieceColor::WHITE)
+///     .piece(Square::from_uci("h1").unwrap(), PieceType::Rook, PieceColor::WHITE)
+///     .piece(Square::from_uci("e8").unwrap(), PieceType::King, PieceColor::BLACK)
+///     .turn(PieceColor::BLACK)
+///     .castling(PieceColor::WHITE, true)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K2R b K - 0 1");
+/// ```
+pub struct BoardBuilder {
+    board: Board,
+    castling: Vec<(PieceColor, bool)>,
+    en_passant: Option<Square>
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        BoardBuilder::new()
+    }
+}
+
+impl BoardBuilder {
+    /// # Creates builder with empty board and white to move
+    pub fn new() -> BoardBuilder {
+        BoardBuilder {
+            board: Board::new_clear(),
+            castling: Vec::new(),
+            en_passant: None
+        }
+    }
+
+    /// # Places piece on given square, replacing piece already standing there
+    pub fn piece(mut self, square: Square, piece_type: PieceType, color: PieceColor) -> BoardBuilder {
+        let mut piece = Piece::new(piece_type, color);
+        let pawn_rank = if color == PieceColor::WHITE { 1 } else { 6 };
+
+        match piece_type {
+            PieceType::Pawn if square.y() != pawn_rank => piece.move_piece(),
+            PieceType::King | PieceType::Rook => piece.move_piece(),
+            _ => ()
+        }
+
+        self.board.put(square.to_index(), Some(piece));
+        self
+    }
+
+    /// # Sets side to move
+    pub fn turn(mut self, color: PieceColor) -> BoardBuilder {
+        self.board.turn = color;
+        self
+    }
+
+    /// # Grants castling right to given color on given side, true for kingside
+    ///
+    /// outermost rook on given side of the king is used
+    pub fn castling(mut self, color: PieceColor, kingside: bool) -> BoardBuilder {
+        self.castling.push((color, kingside));
+        self
+    }
+
+    /// # Sets square skipped by the last double pawn push
+    pub fn en_passant(mut self, square: Square) -> BoardBuilder {
+        self.en_passant = Some(square);
+        self
+    }
+
+    /// # Returns built board
+    ///
+    /// fails if either side doesn't have exactly one king, castling right isn't backed
+    /// by king and rook on home rank, or en passant square isn't behind pawn of the side which just moved,
+    /// castling with king off e file or rook off the corner switches board to Chess960 castling
+    pub fn build(mut self) -> Result<Board, BoardError> {
+        for color in PieceColor::ALL {
+            match self.board.bitboards[bitboard::piece_index(PieceType::King, color)].count_ones() {
+                0 => return Err(BoardError::MissingKing(color)),
+                1 => (),
+                _ => return Err(BoardError::TooManyKings(color))
+            }
+        }
+
+        for (color, kingside) in self.castling {
+            if !self.board.grant_castling(color, kingside) {
+                return Err(BoardError::InvalidCastling(color, kingside));
+            }
+        }
+
+        if let Some(square) = self.en_passant {
+            if !self.board.is_en_passant_possible(square) {
+                return Err(BoardError::InvalidEnPassant(square));
+            }
+
+            self.board.en_passant = Some(square);
+        }
+
+        Ok(self.board)
+    }
+}
+
+fn is_last_rank(square: Square, color: PieceColor) -> bool {
+    match color {
+        PieceColor::WHITE => square.to_coords().1 == 7,
+        PieceColor::BLACK => square.to_coords().1 == 0
+    }
+}
+
+// attack cache counters, see `Board::attack_stats`
+#[cfg(feature = "attack-stats")]
+static ATTACK_QUERIES: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "attack-stats")]
+static ATTACK_COMPUTATIONS: AtomicU64 = AtomicU64::new(0);
+
+// scores used by search
+const MATE_SCORE: i32 = 1_000_000;
+const SCORE_INFINITY: i32 = 2_000_000;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn attack_cache_filled_lazily() {
+        let mut board = Board::from_fen("r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP3PPP/R2QKB1R w KQ - 0 1").unwrap();
+        let cached = |board: &Board| [board.attacks[0].get(), board.attacks[1].get()];
+
+        assert_eq!(cached(&board), [None, None]);
+
+        for _ in 0..10 {
+            assert!(!board.is_in_check(PieceColor::WHITE));
+            assert!(board.is_square_attacked(Square::new(4, 3), PieceColor::BLACK));
+        }
+
+        // only black's attacks were needed
+        let before = cached(&board);
+        assert_eq!(before, [None, Some(board.compute_attacks(PieceColor::BLACK))]);
+
+        // move forgets attacks, they are computed again only when queried
+        assert!(board.apply_move(Move::from_uci("c4d5").unwrap()).is_ok());
+        assert_eq!(cached(&board), [None, None]);
+
+        assert!(!board.is_in_check(PieceColor::BLACK));
+        assert_eq!(cached(&board), [Some(board.compute_attacks(PieceColor::WHITE)), None]);
+
+        // undo restores attacks of the position before the move
+        board.undo();
+        assert_eq!(cached(&board), before);
+    }
 }
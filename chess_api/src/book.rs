@@ -0,0 +1,101 @@
+use core::str::FromStr;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::board::*;
+use super::movement::*;
+
+
+/// # Reasons why opening book could not be loaded
+#[derive(Debug, PartialEq)]
+pub enum BookError {
+    InvalidLine(String), InvalidFen(FenError), InvalidMove(String)
+}
+
+/// # Opening book mapping positions to moves
+///
+/// positions are matched by `Board::repetition_key`, so move clocks don't matter
+#[derive(Default)]
+pub struct OpeningBook {
+    moves: BTreeMap<u64, Vec<Move>>
+}
+
+/// # Loads book from text, one `FEN -> uci_move` entry per line
+///
+/// empty lines and lines starting with `#` are skipped, position may be listed on several lines,
+/// moves must be legal in their position
+///
+/// ```
+/// # use chess_api::book::OpeningBook;
+/// # use chess_api::board::Board;
+/// # use chess_api::movement::Move;
+///
+/// let book: OpeningBook = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 -> e2e4".parse().unwrap();
+///
+/// let board = Board::new();
+///
+/// assert_eq!(book.probe(&board), Some(Move::from_uci("e2e4").unwrap()));
+/// assert_eq!(book.probe(&board).or_else(|| board.best_move(2)), Some(Move::from_uci("e2e4").unwrap()));
+/// ```
+impl FromStr for OpeningBook {
+    type Err = BookError;
+
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        let mut book = OpeningBook::default();
+
+        for line in data.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (fen, uci) = line.split_once("->").ok_or_else(|| BookError::InvalidLine(line.to_string()))?;
+            let board = Board::from_fen(fen.trim()).map_err(BookError::InvalidFen)?;
+            let uci = uci.trim();
+
+            let m = Move::from_uci(uci).ok()
+                .filter(|m| board.is_legal(*m))
+                .ok_or_else(|| BookError::InvalidMove(uci.to_string()))?;
+
+            book.add(&board, m);
+        }
+
+        Ok(book)
+    }
+}
+
+impl OpeningBook {
+    pub fn new() -> OpeningBook {
+        OpeningBook::default()
+    }
+
+    /// # Adds move for given position, moves already listed are not added again
+    pub fn add(&mut self, board: &Board, m: Move) {
+        let moves = self.moves.entry(board.repetition_key()).or_default();
+
+        if !moves.contains(&m) {
+            moves.push(m);
+        }
+    }
+
+    /// # Returns every book move for given position, in order they were added
+    pub fn moves(&self, board: &Board) -> &[Move] {
+        self.moves.get(&board.repetition_key()).map_or(&[], Vec::as_slice)
+    }
+
+    /// # Returns book move for given position, or None if position is not in the book
+    ///
+    /// first listed move is returned, use `moves` to choose between several book moves
+    pub fn probe(&self, board: &Board) -> Option<Move> {
+        self.moves(board).first().copied()
+    }
+
+    /// # Returns number of positions in the book
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+}
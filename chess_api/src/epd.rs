@@ -0,0 +1,118 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::board::*;
+use super::movement::*;
+
+
+/// # Reasons why EPD line could not be parsed
+#[derive(Debug, PartialEq)]
+pub enum EpdError {
+    WrongFieldCount(usize), InvalidFen(FenError), UnterminatedString, InvalidBestMove(String, SanError)
+}
+
+/// # Position with operations, one line of EPD test suite
+pub struct Epd {
+    board: Board,
+    operations: BTreeMap<String, String>,
+    best_moves: Vec<Move>
+}
+
+impl Epd {
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// # Returns operand of every operation keyed by its opcode
+    ///
+    /// quotes around string operands are removed
+    pub fn operations(&self) -> &BTreeMap<String, String> {
+        &self.operations
+    }
+
+    /// # Returns operand of given operation
+    pub fn operation(&self, opcode: &str) -> Option<&str> {
+        self.operations.get(opcode).map(String::as_str)
+    }
+
+    /// # Returns moves listed in `bm` operation
+    pub fn best_moves(&self) -> &[Move] {
+        &self.best_moves
+    }
+}
+
+/// # Parses EPD line
+///
+/// line starts with first four FEN fields followed by operations terminated with semicolons,
+/// best moves in `bm` operation are converted from SAN to moves
+///
+/// ```
+/// # use chess_api::epd;
+/// # use chess_api::movement::Move;
+///
+/// let epd = epd::parse("4k3/8/8/8/8/8/4P3/4K3 w - - bm e4; id \"pawn push\";").unwrap();
+///
+/// assert_eq!(epd.best_moves(), [Move::from_uci("e2e4").unwrap()]);
+/// assert_eq!(epd.operation("id"), Some("pawn push"));
+/// ```
+pub fn parse(line: &str) -> Result<Epd, EpdError> {
+    let mut fields = line.trim().splitn(5, char::is_whitespace);
+    let fen: Vec<&str> = fields.by_ref().take(4).collect();
+
+    if fen.len() < 4 {
+        return Err(EpdError::WrongFieldCount(fen.len()));
+    }
+
+    let board = Board::from_fen(&fen.join(" ")).map_err(EpdError::InvalidFen)?;
+    let mut operations = BTreeMap::new();
+
+    for operation in split_operations(fields.next().unwrap_or(""))? {
+        let (opcode, operand) = operation.split_once(char::is_whitespace).unwrap_or((&operation, ""));
+        let operand = operand.trim();
+        let operand = operand.strip_prefix('"').and_then(|operand| operand.strip_suffix('"')).unwrap_or(operand);
+
+        operations.insert(opcode.to_string(), operand.to_string());
+    }
+
+    let best_moves = match operations.get("bm") {
+        Some(operand) => operand.split_whitespace()
+            .map(|san| board.san_to_move(san).map_err(|e| EpdError::InvalidBestMove(san.to_string(), e)))
+            .collect::<Result<Vec<Move>, EpdError>>()?,
+        None => Vec::new()
+    };
+
+    Ok(Epd { board, operations, best_moves })
+}
+
+/// # Splits operations at semicolons which are not inside quoted string
+fn split_operations(operations: &str) -> Result<Vec<String>, EpdError> {
+    let mut split = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in operations.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            },
+            ';' if !quoted => {
+                split.push(current.trim().to_string());
+                current.clear();
+            },
+            _ => current.push(c)
+        }
+    }
+
+    if quoted {
+        return Err(EpdError::UnterminatedString);
+    }
+
+    // last operation may lack its semicolon
+    if !current.trim().is_empty() {
+        split.push(current.trim().to_string());
+    }
+
+    Ok(split.into_iter().filter(|operation| !operation.is_empty()).collect())
+}
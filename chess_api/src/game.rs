@@ -0,0 +1,666 @@
+use core::fmt;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::board::*;
+use super::movement::*;
+use super::piece::*;
+
+
+/// # Outcome of a game
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum GameResult {
+    WhiteWins, BlackWins, Draw, Ongoing
+}
+
+/// # Prints result as PGN result token
+///
+/// ```
+/// use chess_api::game::GameResult;
+///
+/// assert_eq!(GameResult::WhiteWins.to_string(), "1-0");
+/// assert_eq!(GameResult::Ongoing.to_string(), "*");
+/// ```
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let token = match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Ongoing => "*"
+        };
+
+        write!(f, "{}", token)
+    }
+}
+
+/// # Reasons why PGN could not be loaded
+#[derive(Debug, PartialEq)]
+pub enum PgnError {
+    InvalidTag(String), UnterminatedComment, InvalidMove(String, SanError)
+}
+
+/// # Reasons why move could not be made in a game
+#[derive(Debug, PartialEq)]
+pub enum GameMoveError {
+    GameOver, Illegal(MoveFailReason)
+}
+
+/// # Chess clock holding remaining time of both players
+///
+/// time of the player whose turn is running is counted down from the moment `start_turn` was called,
+/// increment is added when the turn ends in time, `*_at` methods take explicit instant instead of now
+///
+/// ```
+/// # use std::time::{Duration, Instant};
+/// # use chess_api::game::Clock;
+/// # use chess_api::piece::PieceColor;
+///
+/// let mut clock = Clock::new(Duration::from_secs(60), Duration::from_secs(2));
+/// let start = Instant::now();
+///
+/// clock.start_turn_at(PieceColor::WHITE, start);
+/// clock.end_turn_at(start + Duration::from_secs(10));
+///
+/// assert_eq!(clock.remaining(PieceColor::WHITE), Duration::from_secs(52));
+/// assert_eq!(clock.flagged(PieceColor::WHITE), false);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct Clock {
+    remaining: [Duration; 2],
+    increment: Duration,
+    running: Option<(PieceColor, Instant)>
+}
+
+#[cfg(feature = "std")]
+impl Clock {
+    /// # Creates stopped clock giving both players the same time
+    pub fn new(time: Duration, increment: Duration) -> Clock {
+        Clock { remaining: [time; 2], increment, running: None }
+    }
+
+    pub fn increment(&self) -> Duration {
+        self.increment
+    }
+
+    /// # Returns player whose time is running
+    pub fn running(&self) -> Option<PieceColor> {
+        self.running.map(|(color, _)| color)
+    }
+
+    /// # Returns time left to given player, including time spent on running turn
+    pub fn remaining(&self, color: PieceColor) -> Duration {
+        self.remaining_at(color, Instant::now())
+    }
+
+    pub fn remaining_at(&self, color: PieceColor, now: Instant) -> Duration {
+        match self.running {
+            Some((running, started)) if running == color => {
+                self.remaining[color as usize].saturating_sub(now.saturating_duration_since(started))
+            },
+            _ => self.remaining[color as usize]
+        }
+    }
+
+    /// # Starts counting down time of given player
+    ///
+    /// turn running so far is ended first
+    pub fn start_turn(&mut self, color: PieceColor) {
+        self.start_turn_at(color, Instant::now());
+    }
+
+    pub fn start_turn_at(&mut self, color: PieceColor, now: Instant) {
+        self.end_turn_at(now);
+        self.running = Some((color, now));
+    }
+
+    /// # Stops the clock, subtracting time spent on the turn
+    ///
+    /// increment is added only if player still has time left, does nothing if clock is stopped
+    pub fn end_turn(&mut self) {
+        self.end_turn_at(Instant::now());
+    }
+
+    pub fn end_turn_at(&mut self, now: Instant) {
+        if let Some((color, _)) = self.running {
+            let remaining = self.remaining_at(color, now);
+
+            self.remaining[color as usize] = if remaining.is_zero() { remaining } else { remaining + self.increment };
+            self.running = None;
+        }
+    }
+
+    /// # Returns true if given player ran out of time
+    pub fn flagged(&self, color: PieceColor) -> bool {
+        self.flagged_at(color, Instant::now())
+    }
+
+    pub fn flagged_at(&self, color: PieceColor, now: Instant) -> bool {
+        self.remaining_at(color, now).is_zero()
+    }
+}
+
+/// # Chess game
+///
+/// holds board, played moves, PGN tags and result of the game
+pub struct Game {
+    board: Board,
+    moves: Vec<Move>,
+    san: Vec<String>,
+    tags: Vec<(String, String)>,
+    result: GameResult,
+    draw_offer: Option<PieceColor>,
+    #[cfg(feature = "std")]
+    clock: Option<Clock>
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
+impl Game {
+    /// # Creates new game from standard starting position
+    ///
+    /// seven tag roster is filled with unknown values
+    ///
+    /// ```
+    /// # use chess_api::game::{Game, GameResult};
+    ///
+    /// let game = Game::new();
+    ///
+    /// assert_eq!(game.tag("Event"), Some("?"));
+    /// assert_eq!(game.result(), GameResult::Ongoing);
+    /// ```
+    pub fn new() -> Game {
+        let tags = [
+            ("Event", "?"), ("Site", "?"), ("Date", "????.??.??"), ("Round", "?"),
+            ("White", "?"), ("Black", "?"), ("Result", "*")
+        ];
+
+        Game {
+            board: Board::new(),
+            moves: Vec::new(),
+            san: Vec::new(),
+            tags: tags.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+            result: GameResult::Ongoing,
+            draw_offer: None,
+            #[cfg(feature = "std")]
+            clock: None
+        }
+    }
+
+    /// # Loads game from PGN
+    ///
+    /// tag pairs are read and moves in SAN are replayed from standard starting position,
+    /// comments, variations, move numbers and numeric annotation glyphs are skipped,
+    /// move text ends at result token, without it result follows from the final position
+    ///
+    /// ```
+    /// # use chess_api::game::{Game, GameResult};
+    ///
+    /// let game = Game::from_pgn("[White \"Alice\"]\n\n1. e4 {best by test} e5 $1 2. Nf3 1-0").unwrap();
+    ///
+    /// assert_eq!(game.moves().len(), 3);
+    /// assert_eq!(game.tag("White"), Some("Alice"));
+    /// assert_eq!(game.result(), GameResult::WhiteWins);
+    /// ```
+    pub fn from_pgn(pgn: &str) -> Result<Game, PgnError> {
+        let mut game = Game::new();
+        let mut lines = pgn.lines().map(str::trim).peekable();
+
+        while let Some(line) = lines.next_if(|line| line.is_empty() || line.starts_with('[')) {
+            if line.is_empty() {
+                continue;
+            }
+
+            let (name, value) = parse_tag(line).ok_or_else(|| PgnError::InvalidTag(line.to_string()))?;
+            game.set_tag(&name, &value);
+        }
+
+        let movetext = strip_comments(&lines.collect::<Vec<&str>>().join("\n"))?;
+
+        for token in movetext.split_whitespace() {
+            let result = match token {
+                "1-0" => Some(GameResult::WhiteWins),
+                "0-1" => Some(GameResult::BlackWins),
+                "1/2-1/2" => Some(GameResult::Draw),
+                "*" => Some(GameResult::Ongoing),
+                _ => None
+            };
+
+            if let Some(result) = result {
+                game.set_result(result);
+                return Ok(game);
+            }
+
+            if token.starts_with('$') {
+                continue;
+            }
+
+            // move number may be glued to the move, fx: 1.e4, castling written with zeros is not a move number
+            let san = match token.trim_start_matches(|c: char| c.is_ascii_digit()).strip_prefix('.') {
+                Some(rest) => rest.trim_start_matches('.'),
+                None => token
+            };
+
+            if san.is_empty() {
+                continue;
+            }
+
+            let m = game.board.san_to_move(san).map_err(|e| PgnError::InvalidMove(san.to_string(), e))?;
+            // players may continue after drawn position until draw is claimed
+            game.record_move(m).map_err(|_| PgnError::InvalidMove(san.to_string(), SanError::IllegalMove))?;
+        }
+
+        let result = game.outcome_result();
+
+        if result != GameResult::Ongoing {
+            game.set_result(result);
+        }
+
+        Ok(game)
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// # Returns moves played so far
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    pub fn result(&self) -> GameResult {
+        self.result
+    }
+
+    /// # Sets result of the game
+    ///
+    /// used for results not following from the position, fx: resignation or draw by agreement
+    pub fn set_result(&mut self, result: GameResult) {
+        self.result = result;
+        self.set_tag("Result", &result.to_string());
+    }
+
+    /// # Ends the game with given player resigning
+    ///
+    /// does nothing if game is already over
+    ///
+    /// ```
+    /// # use chess_api::game::{Game, GameResult};
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let mut game = Game::new();
+    ///
+    /// game.resign(PieceColor::WHITE);
+    /// assert_eq!(game.result(), GameResult::BlackWins);
+    /// ```
+    pub fn resign(&mut self, color: PieceColor) {
+        if self.result != GameResult::Ongoing {
+            return;
+        }
+
+        self.set_result(match color {
+            PieceColor::WHITE => GameResult::BlackWins,
+            PieceColor::BLACK => GameResult::WhiteWins
+        });
+    }
+
+    /// # Offers draw to the opponent of given player
+    ///
+    /// offer stands until opponent accepts it or makes a move, does nothing if game is already over
+    pub fn offer_draw(&mut self, color: PieceColor) {
+        if self.result == GameResult::Ongoing {
+            self.draw_offer = Some(color);
+        }
+    }
+
+    /// # Returns player whose draw offer is pending
+    pub fn draw_offer(&self) -> Option<PieceColor> {
+        self.draw_offer
+    }
+
+    /// # Accepts opponent's draw offer, ending the game in a draw
+    ///
+    /// returns false if given player has no draw offer to accept
+    ///
+    /// ```
+    /// # use chess_api::game::{Game, GameResult};
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let mut game = Game::new();
+    ///
+    /// game.offer_draw(PieceColor::WHITE);
+    ///
+    /// assert_eq!(game.accept_draw(PieceColor::WHITE), false);
+    /// assert_eq!(game.accept_draw(PieceColor::BLACK), true);
+    /// assert_eq!(game.result(), GameResult::Draw);
+    /// ```
+    pub fn accept_draw(&mut self, color: PieceColor) -> bool {
+        if self.draw_offer != Some(!color) || self.result != GameResult::Ongoing {
+            return false;
+        }
+
+        self.draw_offer = None;
+        self.set_result(GameResult::Draw);
+        true
+    }
+
+    /// # Returns value of given tag
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+
+    /// # Sets value of given tag
+    ///
+    /// new tags are added after already existing ones
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string()))
+        }
+    }
+
+    /// # Performs move and records it
+    ///
+    /// result is set when move ends the game, see `Board::outcome`,
+    /// with clock attached player who ran out of time before moving loses on time and the move is not recorded,
+    /// moves are rejected with `GameMoveError::GameOver` once the game has a result
+    ///
+    /// ```
+    /// # use chess_api::game::{Game, GameResult};
+    /// # use chess_api::movement::Move;
+    ///
+    /// let mut game = Game::new();
+    ///
+    /// for m in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+    ///     assert!(game.make_move(Move::from_uci(m).unwrap()).is_ok());
+    /// }
+    ///
+    /// assert_eq!(game.result(), GameResult::BlackWins);
+    /// ```
+    pub fn make_move(&mut self, m: Move) -> Result<PostMoveState, GameMoveError> {
+        if self.result != GameResult::Ongoing {
+            return Err(GameMoveError::GameOver);
+        }
+
+        // move made after flag fell doesn't count
+        #[cfg(feature = "std")]
+        if self.check_flag() {
+            return Err(GameMoveError::GameOver);
+        }
+
+        let state = self.record_move(m).map_err(GameMoveError::Illegal)?;
+
+        let result = self.outcome_result();
+
+        if result != GameResult::Ongoing {
+            self.set_result(result);
+        }
+
+        #[cfg(feature = "std")]
+        self.press_clock(self.board.turn());
+
+        Ok(state)
+    }
+
+    /// # Attaches clock to the game and starts time of the side to move
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use chess_api::game::{Clock, Game};
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let mut game = Game::new();
+    ///
+    /// game.set_clock(Clock::new(Duration::from_secs(180), Duration::from_secs(2)));
+    ///
+    /// assert_eq!(game.clock().and_then(Clock::running), Some(PieceColor::WHITE));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn set_clock(&mut self, mut clock: Clock) {
+        if self.result == GameResult::Ongoing {
+            clock.start_turn(self.board.turn());
+        }
+
+        self.clock = Some(clock);
+    }
+
+    #[cfg(feature = "std")]
+    pub fn clock(&self) -> Option<&Clock> {
+        self.clock.as_ref()
+    }
+
+    /// # Ends the game if side to move ran out of time
+    ///
+    /// game is lost on time, or drawn when opponent doesn't have mating material,
+    /// returns true if game ended this way
+    #[cfg(feature = "std")]
+    pub fn check_flag(&mut self) -> bool {
+        let color = self.board.turn();
+
+        if self.result != GameResult::Ongoing || !self.clock.as_ref().is_some_and(|clock| clock.flagged(color)) {
+            return false;
+        }
+
+        self.time_out(color);
+        true
+    }
+
+    /// # Takes back the last move
+    ///
+    /// game is ongoing again afterwards, pending draw offer is withdrawn and clock runs for the side to move,
+    /// returns the move taken back or None if no moves were played,
+    /// game ended other than by position on board, fx: by resignation, can't be taken back
+    ///
+    /// ```
+    /// # use chess_api::game::{Game, GameResult};
+    /// # use chess_api::movement::Move;
+    ///
+    /// let mut game = Game::new();
+    ///
+    /// for m in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+    ///     assert!(game.make_move(Move::from_uci(m).unwrap()).is_ok());
+    /// }
+    ///
+    /// assert_eq!(game.undo(), Some(Move::from_uci("d8h4").unwrap()));
+    /// assert_eq!(game.result(), GameResult::Ongoing);
+    /// assert_eq!(game.moves().len(), 3);
+    /// ```
+    pub fn undo(&mut self) -> Option<Move> {
+        if self.result != GameResult::Ongoing && self.result != self.outcome_result() {
+            return None;
+        }
+
+        let m = self.board.undo()?;
+
+        self.moves.pop();
+        self.san.pop();
+        self.draw_offer = None;
+        self.set_result(GameResult::Ongoing);
+
+        #[cfg(feature = "std")]
+        if let Some(clock) = self.clock.as_mut() {
+            clock.start_turn(self.board.turn());
+        }
+
+        Some(m)
+    }
+
+    /// # Returns types of pieces captured by given player, in order of capture
+    ///
+    /// ```
+    /// # use chess_api::game::Game;
+    /// # use chess_api::movement::Move;
+    /// # use chess_api::piece::{PieceColor, PieceType};
+    ///
+    /// let mut game = Game::new();
+    ///
+    /// for m in ["e2e4", "d7d5", "e4d5"] {
+    ///     assert!(game.make_move(Move::from_uci(m).unwrap()).is_ok());
+    /// }
+    ///
+    /// assert_eq!(game.captured_by(PieceColor::WHITE), [PieceType::Pawn]);
+    /// assert!(game.captured_by(PieceColor::BLACK).is_empty());
+    /// ```
+    pub fn captured_by(&self, color: PieceColor) -> Vec<PieceType> {
+        self.board.captured_by(color).map(|piece| piece.piece_type()).collect()
+    }
+
+    /// # Performs move and records it without looking at result of the game
+    fn record_move(&mut self, m: Move) -> Result<PostMoveState, MoveFailReason> {
+        let san = self.board.move_to_san(m);
+        let mover = self.board.turn();
+        let state = self.board.perform_move(m)?;
+
+        self.moves.push(m);
+        self.san.push(san);
+
+        // making a move declines opponent's draw offer
+        if self.draw_offer.is_some_and(|color| color != mover) {
+            self.draw_offer = None;
+        }
+
+        Ok(state)
+    }
+
+    /// # Returns result following from the position on board
+    fn outcome_result(&self) -> GameResult {
+        match self.board.outcome() {
+            Some(Outcome::Checkmate(PieceColor::WHITE)) => GameResult::WhiteWins,
+            Some(Outcome::Checkmate(PieceColor::BLACK)) => GameResult::BlackWins,
+            Some(_) => GameResult::Draw,
+            None => GameResult::Ongoing
+        }
+    }
+
+    /// # Starts time of given player, or stops the clock if game is over
+    #[cfg(feature = "std")]
+    fn press_clock(&mut self, color: PieceColor) {
+        let ongoing = self.result == GameResult::Ongoing;
+
+        if let Some(clock) = self.clock.as_mut() {
+            if ongoing { clock.start_turn(color) } else { clock.end_turn() }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn time_out(&mut self, color: PieceColor) {
+        if let Some(clock) = self.clock.as_mut() {
+            clock.end_turn();
+        }
+
+        self.set_result(match color {
+            _ if !self.board.has_mating_material(!color) => GameResult::Draw,
+            PieceColor::WHITE => GameResult::BlackWins,
+            PieceColor::BLACK => GameResult::WhiteWins
+        });
+    }
+
+    /// # Returns game written in PGN
+    ///
+    /// tag pairs are followed by move text in SAN ending with result token,
+    /// move text lines are wrapped at 80 characters
+    ///
+    /// ```
+    /// # use chess_api::game::Game;
+    /// # use chess_api::movement::Move;
+    ///
+    /// let mut game = Game::new();
+    ///
+    /// for m in ["e2e4", "e7e5", "g1f3"] {
+    ///     assert!(game.make_move(Move::from_uci(m).unwrap()).is_ok());
+    /// }
+    ///
+    /// assert!(game.to_pgn().ends_with("\n\n1. e4 e5 2. Nf3 *\n"));
+    /// ```
+    pub fn to_pgn(&self) -> String {
+        let mut pgn = String::new();
+
+        for (name, value) in self.tags.iter() {
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, value.replace('\\', "\\\\").replace('"', "\\\"")));
+        }
+
+        pgn.push('\n');
+
+        let mut tokens = Vec::new();
+
+        for (i, san) in self.san.iter().enumerate() {
+            if i % 2 == 0 {
+                tokens.push(format!("{}.", i / 2 + 1));
+            }
+
+            tokens.push(san.clone());
+        }
+
+        tokens.push(self.result.to_string());
+
+        let mut line = String::new();
+
+        for token in tokens {
+            if !line.is_empty() && line.len() + 1 + token.len() > 80 {
+                pgn.push_str(&line);
+                pgn.push('\n');
+                line.clear();
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+
+            line.push_str(&token);
+        }
+
+        pgn.push_str(&line);
+        pgn.push('\n');
+        pgn
+    }
+}
+
+/// # Parses tag pair line, fx: [Event "?"]
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?.trim();
+    let (name, value) = inner.split_once(char::is_whitespace)?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+
+    Some((name.to_string(), value.replace("\\\"", "\"").replace("\\\\", "\\")))
+}
+
+/// # Removes brace and line comments and variations from move text
+fn strip_comments(movetext: &str) -> Result<String, PgnError> {
+    let mut stripped = String::new();
+    let mut chars = movetext.chars();
+    let mut variation_depth = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                chars.by_ref().find(|c| *c == '}').ok_or(PgnError::UnterminatedComment)?;
+                stripped.push(' ');
+            },
+            ';' => {
+                chars.by_ref().find(|c| *c == '\n');
+                stripped.push(' ');
+            },
+            '(' => variation_depth += 1,
+            ')' if variation_depth > 0 => {
+                variation_depth -= 1;
+                stripped.push(' ');
+            },
+            _ if variation_depth > 0 => (),
+            _ => stripped.push(c)
+        }
+    }
+
+    if variation_depth > 0 {
+        return Err(PgnError::UnterminatedComment);
+    }
+
+    Ok(stripped)
+}
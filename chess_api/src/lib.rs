@@ -1,4 +1,21 @@
+//! # Chess rules and board representation
+//!
+//! crate is `no_std` compatible when default `std` feature is disabled, `alloc` is still required,
+//! board renderers (`Board::render` and related methods) are only available with `std` feature
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod board;
 pub mod piece;
 pub mod movement;
+pub mod game;
+pub mod uci;
+pub mod epd;
+pub mod book;
 
+mod zobrist;
+mod bitboard;
+#[cfg(feature = "std")]
+mod render;
@@ -1,3 +1,27 @@
+use core::fmt;
+use alloc::format;
+use alloc::string::String;
+
+use crate::piece::PieceType;
+
+/// # Reasons why square could not be created
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum SquareError {
+    InvalidLength(usize), InvalidFile(char), InvalidRank(char), OutOfBounds(u8, u8), IndexOutOfBounds(usize)
+}
+
+/// # Reasons why move could not be created
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum MoveError {
+    InvalidLength(usize), InvalidSquare(SquareError), InvalidPromotion(char), NullMove
+}
+
+impl From<SquareError> for MoveError {
+    fn from(e: SquareError) -> Self {
+        MoveError::InvalidSquare(e)
+    }
+}
+
 /// # Move's square struct
 ///
 /// holds information about move's start or end
@@ -9,7 +33,7 @@
 ///
 /// let _s = Square::new(0, 0);
 /// ```
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(PartialEq, Copy, Clone)]
 pub struct Square {
     x: u8,
     y: u8,
@@ -20,65 +44,357 @@ impl Square {
     ///
     /// note: `x` and `y` are 0 based 
     ///
+    /// panics if coordinates are out of board, see `try_new`,
+    /// can be used in const context
+    ///
     /// ```
     /// use chess_api::movement::Square;
     ///
     /// let s = Square::new(0, 1);
     /// assert_eq!(s.to_uci(), "a2");
+    ///
+    /// const E4: Square = Square::new(4, 3);
+    /// assert_eq!(E4.to_uci(), "e4");
     /// ```
-    pub fn new(x: u8, y: u8) -> Square {
-        assert!(x < 8);
-        assert!(y < 8);
+    pub const fn new(x: u8, y: u8) -> Square {
+        assert!(x < 8 && y < 8, "square coordinates out of board");
 
-        Square {
-            x, y   
+        Square { x, y }
+    }
+
+    /// # Square's fallible constructor
+    ///
+    /// returns error carrying given coordinates if they are out of board
+    ///
+    /// ```
+    /// use chess_api::movement::{Square, SquareError};
+    ///
+    /// assert_eq!(Square::try_new(0, 1), Ok(Square::new(0, 1)));
+    /// assert_eq!(Square::try_new(8, 1), Err(SquareError::OutOfBounds(8, 1)));
+    /// ```
+    pub fn try_new(x: u8, y: u8) -> Result<Square, SquareError> {
+        if x < 8 && y < 8 {
+            Ok(Square { x, y })
+        } else {
+            Err(SquareError::OutOfBounds(x, y))
         }
     }
 
-    pub fn from_index(i: usize) -> Square {
-        assert!(i < 64);
+    /// # Creates square from its index
+    ///
+    /// inverse of `to_index`, index is `x + 8 * y`, returns error for indices out of board
+    ///
+    /// ```
+    /// use chess_api::movement::{Square, SquareError};
+    ///
+    /// assert_eq!(Square::from_index(12), Ok(Square::new(4, 1)));
+    /// assert_eq!(Square::from_index(64), Err(SquareError::IndexOutOfBounds(64)));
+    /// ```
+    pub fn from_index(i: usize) -> Result<Square, SquareError> {
+        if i < 64 {
+            Ok(Square {
+                x: (i % 8) as u8,
+                y: (i / 8) as u8
+            })
+        } else {
+            Err(SquareError::IndexOutOfBounds(i))
+        }
+    }
 
-        Square {
-            x: (i % 8) as u8,
-            y: (i / 8) as u8
+    /// # Parses square from uci notation
+    ///
+    /// inverse of `to_uci`, file letter is case insensitive
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// assert_eq!(Square::from_uci("a2").unwrap().to_uci(), "a2");
+    /// assert_eq!(Square::from_uci("E4").unwrap(), Square::new(4, 3));
+    /// assert!(Square::from_uci("i1").is_err());
+    /// ```
+    pub fn from_uci(s: &str) -> Result<Square, SquareError> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(file), Some(rank), None) => {
+                let x = match file.to_ascii_lowercase() {
+                    f @ 'a'..='h' => f as u8 - b'a',
+                    _ => return Err(SquareError::InvalidFile(file))
+                };
+
+                let y = match rank {
+                    '1'..='8' => rank as u8 - b'1',
+                    _ => return Err(SquareError::InvalidRank(rank))
+                };
+
+                Ok(Square { x, y })
+            },
+            _ => Err(SquareError::InvalidLength(s.chars().count()))
         }
     }
 
     pub fn to_uci(&self) -> String {
-        format!("{}{}", "abcdefgh".chars().nth(self.x.into()).unwrap(), self.y + 1)
+        format!("{}{}", self.file(), self.y + 1)
     }
 
-    pub fn to_index(&self) -> usize {
-        (self.x + 8 * self.y).into()
+    /// # Returns 0 based file coordinate
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// assert_eq!(Square::from_uci("e2").unwrap().x(), 4);
+    /// ```
+    pub fn x(&self) -> u8 {
+        self.x
+    }
+
+    /// # Returns 0 based rank coordinate
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// assert_eq!(Square::from_uci("e2").unwrap().y(), 1);
+    /// ```
+    pub fn y(&self) -> u8 {
+        self.y
+    }
+
+    /// # Returns file letter, 'a' to 'h'
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// assert_eq!(Square::new(4, 1).file(), 'e');
+    /// ```
+    pub fn file(&self) -> char {
+        (b'a' + self.x) as char
+    }
+
+    pub const fn to_index(&self) -> usize {
+        (self.x + 8 * self.y) as usize
     }
 
     pub fn to_coords(&self) -> (u8, u8) {
         (self.x, self.y)
     }
+
+    /// # Returns Manhattan distance to other square
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// assert_eq!(Square::new(0, 0).distance(&Square::new(2, 1)), 3);
+    /// ```
+    pub fn distance(&self, other: &Square) -> u8 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// # Returns Chebyshev distance to other square
+    ///
+    /// it is the number of king moves needed to get from one square to the other
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// assert_eq!(Square::new(0, 0).chebyshev_distance(&Square::new(2, 1)), 2);
+    /// ```
+    pub fn chebyshev_distance(&self, other: &Square) -> u8 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// # Returns true if square is light
+    ///
+    /// follows the board convention where a1 is dark, so square is light when `x + y` is odd
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// assert_eq!(Square::from_uci("a1").unwrap().is_light(), false);
+    /// assert_eq!(Square::from_uci("h1").unwrap().is_light(), true);
+    /// ```
+    pub fn is_light(&self) -> bool {
+        (self.x + self.y) % 2 == 1
+    }
+
+    /// # Returns squares strictly between two squares, going from first toward second
+    ///
+    /// squares have to lie on the same rank, file or diagonal, otherwise nothing is returned
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// let a1 = Square::from_uci("a1").unwrap();
+    ///
+    /// assert_eq!(Square::between(a1, Square::from_uci("d4").unwrap()).count(), 2);
+    /// assert_eq!(Square::between(a1, Square::from_uci("b3").unwrap()).count(), 0);
+    /// ```
+    pub fn between(a: Square, b: Square) -> impl Iterator<Item = Square> {
+        let m = Move::new(a, b);
+        let aligned = m.is_straight() || m.is_diagonal();
+
+        LineMovement::new(a, b).take_while(move |_| aligned)
+    }
+
+    /// # Returns other squares on the same rank, from a to h
+    ///
+    /// square itself is not included
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// let rank: Vec<String> = Square::from_uci("d4").unwrap().same_rank().map(|square| square.to_uci()).collect();
+    ///
+    /// assert_eq!(rank, ["a4", "b4", "c4", "e4", "f4", "g4", "h4"]);
+    /// ```
+    pub fn same_rank(&self) -> impl Iterator<Item = Square> {
+        let (x, y) = (self.x, self.y);
+        (0..8).filter(move |file| *file != x).map(move |file| Square::new(file, y))
+    }
+
+    /// # Returns other squares on the same file, from 1 to 8
+    ///
+    /// square itself is not included
+    ///
+    /// ```
+    /// use chess_api::movement::Square;
+    ///
+    /// let file: Vec<String> = Square::from_uci("d4").unwrap().same_file().map(|square| square.to_uci()).collect();
+    ///
+    /// assert_eq!(file, ["d1", "d2", "d3", "d5", "d6", "d7", "d8"]);
+    /// ```
+    pub fn same_file(&self) -> impl Iterator<Item = Square> {
+        let (x, y) = (self.x, self.y);
+        (0..8).filter(move |rank| *rank != y).map(move |rank| Square::new(x, rank))
+    }
+}
+
+/// # Prints square in uci notation
+///
+/// ```
+/// use chess_api::movement::Square;
+///
+/// assert_eq!(Square::new(4, 1).to_string(), "e2");
+/// ```
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_uci())
+    }
+}
+
+impl fmt::Debug for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Square({})", self.to_uci())
+    }
 }
 
 fn min_max<T: PartialOrd>(a: T, b: T) -> (T, T) {
     if a < b { (a, b) } else { (b, a) }
 }
 
-#[derive(Copy, Clone)]
+#[derive(PartialEq, Copy, Clone)]
 pub struct Move {
     start: Square,
     end: Square,
+    promotion: Option<PieceType>,
 }
 
 impl Move {
-    pub fn new(start: Square, end: Square) -> Move {
-        assert_ne!(start, end);
+    /// # Move's constructor
+    ///
+    /// start and end can be the same square, such null move is never possible for any piece
+    ///
+    /// ```
+    /// use chess_api::movement::{Move, Square};
+    ///
+    /// let m = Move::new(Square::new(4, 1), Square::new(4, 3));
+    /// assert_eq!(m.to_uci(), "e2e4");
+    /// ```
+    pub const fn new(start: Square, end: Square) -> Move {
+        Move {
+            start, end, promotion: None
+        }
+    }
 
+    /// # Creates move promoting pawn to given piece type
+    ///
+    /// ```
+    /// use chess_api::movement::{Move, Square};
+    /// use chess_api::piece::PieceType;
+    ///
+    /// let m = Move::with_promotion(Square::new(4, 6), Square::new(4, 7), PieceType::Queen);
+    ///
+    /// assert_eq!(m.promotion(), Some(PieceType::Queen));
+    /// assert_eq!(m.to_uci(), "e7e8q");
+    /// ```
+    pub fn with_promotion(start: Square, end: Square, promotion: PieceType) -> Move {
         Move {
-            start, end
+            promotion: Some(promotion), ..Move::new(start, end)
+        }
+    }
+
+    /// # Parses move from uci notation
+    ///
+    /// optional fifth character is the promotion piece (`q`, `r`, `b` or `n`)
+    ///
+    /// ```
+    /// use chess_api::movement::{Move, Square};
+    /// use chess_api::piece::PieceType;
+    ///
+    /// let m = Move::from_uci("e2e4").unwrap();
+    ///
+    /// assert_eq!(m.start(), Square::new(4, 1));
+    /// assert_eq!(m.end(), Square::new(4, 3));
+    /// assert_eq!(m.to_uci(), "e2e4");
+    ///
+    /// assert_eq!(Move::from_uci("e7e8q").unwrap().promotion(), Some(PieceType::Queen));
+    /// assert!(Move::from_uci("e2e").is_err());
+    /// ```
+    pub fn from_uci(s: &str) -> Result<Move, MoveError> {
+        let len = s.chars().count();
+
+        if len != 4 && len != 5 {
+            return Err(MoveError::InvalidLength(len));
+        }
+
+        let (start, rest) = s.split_at(s.char_indices().nth(2).unwrap().0);
+        let (end, promotion) = rest.split_at(rest.char_indices().nth(2).map_or(rest.len(), |(i, _)| i));
+
+        let start = Square::from_uci(start)?;
+        let end = Square::from_uci(end)?;
+
+        if start == end {
+            return Err(MoveError::NullMove);
         }
+
+        let promotion = match promotion.chars().next() {
+            Some(c) => match c.to_ascii_lowercase() {
+                'q' => Some(PieceType::Queen),
+                'r' => Some(PieceType::Rook),
+                'b' => Some(PieceType::Bishop),
+                'n' => Some(PieceType::Knight),
+                _ => return Err(MoveError::InvalidPromotion(c))
+            },
+            None => None
+        };
+
+        Ok(Move { start, end, promotion })
     }
 
     pub fn to_uci(&self) -> String {
         let mut result = self.start.to_uci();
         result.push_str(&self.end.to_uci());
+
+        if let Some(promotion) = self.promotion {
+            result.push(match promotion {
+                PieceType::Queen => 'q',
+                PieceType::Rook => 'r',
+                PieceType::Bishop => 'b',
+                PieceType::Knight => 'n',
+                PieceType::King => 'k',
+                PieceType::Pawn => 'p'
+            });
+        }
+
         result
     }
 
@@ -94,16 +410,78 @@ impl Move {
         (self.min_max_x(), self.min_max_y())
     }
 
+    /// # Returns absolute file and rank distances between move's start and end
+    ///
+    /// ```
+    /// use chess_api::movement::Move;
+    ///
+    /// // knight moves
+    /// assert_eq!(Move::from_uci("g1f3").unwrap().to_deltas(), (1, 2));
+    /// assert_eq!(Move::from_uci("b1d2").unwrap().to_deltas(), (2, 1));
+    ///
+    /// // direction does not matter
+    /// assert_eq!(Move::from_uci("f3g1").unwrap().to_deltas(), (1, 2));
+    /// ```
     pub fn to_deltas(&self) -> (u8, u8) {
         let (min_x, max_x) = self.min_max_x();
         let (min_y, max_y) = self.min_max_y();
         (max_x - min_x, max_y - min_y)
     }
 
+    /// # Returns unit step from move's start toward its end
+    ///
+    /// each axis is -1, 0 or 1, zero-length move has direction (0, 0)
+    ///
+    /// ```
+    /// use chess_api::movement::Move;
+    ///
+    /// assert_eq!(Move::from_uci("a1h8").unwrap().direction(), (1, 1));
+    /// assert_eq!(Move::from_uci("e4e1").unwrap().direction(), (0, -1));
+    /// assert_eq!(Move::from_uci("g1f3").unwrap().direction(), (-1, 1));
+    /// ```
+    pub fn direction(&self) -> (i8, i8) {
+        let dx = self.end.x as i8 - self.start.x as i8;
+        let dy = self.end.y as i8 - self.start.y as i8;
+        (dx.signum(), dy.signum())
+    }
+
+    /// # Checks if move goes along a diagonal
+    ///
+    /// ```
+    /// use chess_api::movement::Move;
+    ///
+    /// assert!(Move::from_uci("c1h6").unwrap().is_diagonal());
+    /// assert!(!Move::from_uci("g1f3").unwrap().is_diagonal());
+    /// ```
+    pub fn is_diagonal(&self) -> bool {
+        let (dx, dy) = self.to_deltas();
+        dx == dy && dx != 0
+    }
+
+    /// # Checks if move goes along a file or a rank
+    ///
+    /// ```
+    /// use chess_api::movement::Move;
+    ///
+    /// assert!(Move::from_uci("a1a8").unwrap().is_straight());
+    /// assert!(!Move::from_uci("a1b2").unwrap().is_straight());
+    /// ```
+    pub fn is_straight(&self) -> bool {
+        let (dx, dy) = self.to_deltas();
+        (dx == 0) != (dy == 0)
+    }
+
     pub fn to_squares(&self) -> (Square, Square) {
         (self.start, self.end)
     }
 
+    /// # Returns 0 based coordinates of move's start and end
+    ///
+    /// ```
+    /// use chess_api::movement::Move;
+    ///
+    /// assert_eq!(Move::from_uci("g1f3").unwrap().to_coords(), ((6, 0), (5, 2)));
+    /// ```
     pub fn to_coords(&self) -> ((u8, u8), (u8, u8)) {
         (self.start.to_coords(), self.end.to_coords())
     }
@@ -115,6 +493,30 @@ impl Move {
     pub fn end(&self) -> Square {
         self.end
     }
+
+    /// # Returns piece type pawn is promoted to, if any
+    pub fn promotion(&self) -> Option<PieceType> {
+        self.promotion
+    }
+}
+
+/// # Prints move in uci notation
+///
+/// ```
+/// use chess_api::movement::Move;
+///
+/// assert_eq!(Move::from_uci("e7e8q").unwrap().to_string(), "e7e8q");
+/// ```
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_uci())
+    }
+}
+
+impl fmt::Debug for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Move({})", self.to_uci())
+    }
 }
 
 pub struct LineMovement {
@@ -164,6 +566,7 @@ impl Iterator for LineMovement {
 #[cfg(test)]
 mod test {
     use super::*;
+    use alloc::vec::Vec;
 
     #[test]
     fn line_movement() {
@@ -200,6 +603,83 @@ mod test {
         assert_eq!(movement.next(), None);
     }
 
+    #[test]
+    fn squares_between() {
+        let between = |a: &str, b: &str| Square::between(Square::from_uci(a).unwrap(), Square::from_uci(b).unwrap())
+            .map(|square| square.to_uci())
+            .collect::<Vec<String>>();
+
+        assert_eq!(between("a1", "a5"), ["a2", "a3", "a4"]);
+        assert_eq!(between("h4", "d4"), ["g4", "f4", "e4"]);
+        assert_eq!(between("f6", "c3"), ["e5", "d4"]);
+        assert_eq!(between("b2", "c3"), Vec::<String>::new());
+        assert_eq!(between("a1", "b3"), Vec::<String>::new());
+        assert_eq!(between("a1", "h7"), Vec::<String>::new());
+        assert_eq!(between("e4", "e4"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn display_and_debug() {
+        let m = Move::from_uci("e2e4").unwrap();
+
+        assert_eq!(format!("{}", m.start()), "e2");
+        assert_eq!(format!("{:?}", m.start()), "Square(e2)");
+        assert_eq!(format!("{}", m), "e2e4");
+        assert_eq!(format!("{:?}", m), "Move(e2e4)");
+    }
+
+    #[test]
+    fn square_distance() {
+        let a1 = Square::new(0, 0);
+        let h1 = Square::new(7, 0);
+        let a8 = Square::new(0, 7);
+        let h8 = Square::new(7, 7);
+
+        assert_eq!(a1.distance(&h8), 14);
+        assert_eq!(h8.distance(&a1), 14);
+        assert_eq!(a1.distance(&h1), 7);
+        assert_eq!(h1.distance(&a8), 14);
+        assert_eq!(a1.distance(&a1), 0);
+
+        assert_eq!(a1.chebyshev_distance(&h8), 7);
+        assert_eq!(h8.chebyshev_distance(&a1), 7);
+        assert_eq!(a1.chebyshev_distance(&h1), 7);
+        assert_eq!(h1.chebyshev_distance(&a8), 7);
+        assert_eq!(a1.chebyshev_distance(&a1), 0);
+    }
+
+    #[test]
+    fn const_construction() {
+        const CORNERS: [Square; 4] = [Square::new(0, 0), Square::new(7, 0), Square::new(0, 7), Square::new(7, 7)];
+        const A1_INDEX: usize = CORNERS[0].to_index();
+        const LONG_DIAGONAL: Move = Move::new(CORNERS[0], CORNERS[3]);
+
+        assert_eq!(CORNERS.map(|square| square.to_index()), [0, 7, 56, 63]);
+        assert_eq!(A1_INDEX, 0);
+        assert_eq!(LONG_DIAGONAL.to_uci(), "a1h8");
+    }
+
+    #[test]
+    fn move_direction() {
+        let m = Move::new(Square::new(3, 3), Square::new(0, 6));
+
+        assert_eq!(m.direction(), (-1, 1));
+        assert!(m.is_diagonal());
+        assert!(!m.is_straight());
+
+        let m = Move::new(Square::new(3, 3), Square::new(7, 3));
+
+        assert_eq!(m.direction(), (1, 0));
+        assert!(!m.is_diagonal());
+        assert!(m.is_straight());
+
+        let m = Move { start: Square::new(3, 3), end: Square::new(3, 3), promotion: None };
+
+        assert_eq!(m.direction(), (0, 0));
+        assert!(!m.is_diagonal());
+        assert!(!m.is_straight());
+    }
+
     #[test]
     fn uci_format_test() {
         let s = Move::new(Square::new(0, 0), Square::new(7, 7));
@@ -209,6 +689,72 @@ mod test {
         assert_eq!(s.to_uci(), "b3d5");
     }
 
+    #[test]
+    fn square_from_uci() {
+        for i in 0..64 {
+            let s = Square::from_index(i).unwrap();
+            assert_eq!(Square::from_uci(&s.to_uci()), Ok(s));
+        }
+
+        assert_eq!(Square::from_uci("H8"), Ok(Square::new(7, 7)));
+        assert_eq!(Square::from_uci("e"), Err(SquareError::InvalidLength(1)));
+        assert_eq!(Square::from_uci("e44"), Err(SquareError::InvalidLength(3)));
+        assert_eq!(Square::from_uci("i4"), Err(SquareError::InvalidFile('i')));
+        assert_eq!(Square::from_uci("e9"), Err(SquareError::InvalidRank('9')));
+        assert_eq!(Square::from_uci("e0"), Err(SquareError::InvalidRank('0')));
+    }
+
+    #[test]
+    fn square_try_new() {
+        assert_eq!(Square::try_new(7, 7), Ok(Square::new(7, 7)));
+        assert_eq!(Square::try_new(8, 0), Err(SquareError::OutOfBounds(8, 0)));
+        assert_eq!(Square::try_new(0, 8), Err(SquareError::OutOfBounds(0, 8)));
+        assert_eq!(Square::try_new(255, 255), Err(SquareError::OutOfBounds(255, 255)));
+    }
+
+    #[test]
+    fn square_from_index() {
+        for x in 0..8 {
+            for y in 0..8 {
+                let s = Square::new(x, y);
+                assert_eq!(Square::from_index(s.to_index()).unwrap(), s);
+            }
+        }
+
+        for i in 0..64 {
+            assert_eq!(Square::from_index(i).unwrap().to_index(), i);
+        }
+
+        assert_eq!(Square::from_index(64), Err(SquareError::IndexOutOfBounds(64)));
+        assert_eq!(Square::from_index(usize::MAX), Err(SquareError::IndexOutOfBounds(usize::MAX)));
+    }
+
+    #[test]
+    fn square_color() {
+        assert!(!Square::from_uci("a1").unwrap().is_light());
+        assert!(Square::from_uci("h1").unwrap().is_light());
+        assert!(Square::from_uci("a8").unwrap().is_light());
+        assert!(!Square::from_uci("h8").unwrap().is_light());
+    }
+
+    #[test]
+    fn move_from_uci() {
+        assert_eq!(Move::from_uci("e2e4").unwrap().to_uci(), "e2e4");
+        assert_eq!(Move::from_uci("a1h8"), Ok(Move::new(Square::new(0, 0), Square::new(7, 7))));
+
+        let m = Move::from_uci("b7b8n").unwrap();
+        assert_eq!(m.to_squares(), (Square::new(1, 6), Square::new(1, 7)));
+        assert_eq!(m.promotion(), Some(PieceType::Knight));
+        assert_eq!(m.to_uci(), "b7b8n");
+
+        assert_eq!(Move::from_uci("e2"), Err(MoveError::InvalidLength(2)));
+        assert_eq!(Move::from_uci("e2e4qq"), Err(MoveError::InvalidLength(6)));
+        assert_eq!(Move::from_uci("e2e9"), Err(MoveError::InvalidSquare(SquareError::InvalidRank('9'))));
+        assert_eq!(Move::from_uci("z2e4"), Err(MoveError::InvalidSquare(SquareError::InvalidFile('z'))));
+        assert_eq!(Move::from_uci("e7e8x"), Err(MoveError::InvalidPromotion('x')));
+        assert_eq!(Move::from_uci("e2e2"), Err(MoveError::NullMove));
+    }
+
     #[test]
     #[should_panic]
     fn square_x_overflow() {
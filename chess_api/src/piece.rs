@@ -1,5 +1,6 @@
+use crate::bitboard;
 use crate::movement::Move;
-use std::ops::Not;
+use core::ops::Not;
 
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -8,6 +9,11 @@ pub enum PieceColor {
     BLACK
 }
 
+impl PieceColor {
+    /// # Both colors, white first
+    pub const ALL: [PieceColor; 2] = [PieceColor::WHITE, PieceColor::BLACK];
+}
+
 impl Not for PieceColor {
     type Output = Self;
 
@@ -19,12 +25,57 @@ impl Not for PieceColor {
     }
 }
 
+/// # Kind of move
+///
+/// `Sliding` and `PawnDouble` moves need clear path between start and end
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum MoveKind {
+    Illegal, Normal, Sliding, PawnDouble, EnPassant, Castle, Promotion
+}
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum PieceType {
     Pawn, Rook, Knight, Bishop, Queen, King
 }
 
-#[derive(Debug)]
+impl PieceType {
+    /// # Every piece type, ordered by value: pawn, knight, bishop, rook, queen, king
+    ///
+    /// ```
+    /// use chess_api::piece::PieceType;
+    ///
+    /// let values: Vec<i32> = PieceType::ALL[..5].iter().map(|piece_type| piece_type.value()).collect();
+    ///
+    /// assert!(values.is_sorted());
+    /// ```
+    pub const ALL: [PieceType; 6] = [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen, PieceType::King];
+
+    /// # Returns piece type's value in centipawns
+    ///
+    /// king is worth 0 as it can never be captured
+    ///
+    /// ```
+    /// use chess_api::piece::PieceType;
+    ///
+    /// assert_eq!(PieceType::Pawn.value(), 100);
+    /// assert_eq!(PieceType::Queen.value(), 900);
+    /// ```
+    pub fn value(&self) -> i32 {
+        match self {
+            PieceType::Pawn => 100,
+            PieceType::Knight => 320,
+            PieceType::Bishop => 330,
+            PieceType::Rook => 500,
+            PieceType::Queen => 900,
+            PieceType::King => 0
+        }
+    }
+}
+
+/// # Chess piece
+///
+/// small copyable value, board stores pieces directly without allocation
+#[derive(Debug, Copy, Clone)]
 pub struct Piece {
     piece_type: PieceType,
     piece_color: PieceColor,
@@ -43,49 +94,106 @@ impl Piece {
         }
     }
 
+    /// # Parses piece from FEN letter
+    ///
+    /// uppercase letters are white pieces and lowercase letters are black pieces,
+    /// inverse of `get_character`
+    ///
+    /// ```
+    /// use chess_api::piece::{Piece, PieceType, PieceColor};
+    ///
+    /// let piece = Piece::from_char('Q').unwrap();
+    ///
+    /// assert_eq!(piece.piece_type(), PieceType::Queen);
+    /// assert_eq!(piece.color(), PieceColor::WHITE);
+    /// assert!(Piece::from_char('x').is_none());
+    /// ```
+    pub fn from_char(c: char) -> Option<Piece> {
+        let piece_type = match c.to_ascii_lowercase() {
+            'p' => PieceType::Pawn,
+            'n' => PieceType::Knight,
+            'b' => PieceType::Bishop,
+            'r' => PieceType::Rook,
+            'q' => PieceType::Queen,
+            'k' => PieceType::King,
+            _ => return None
+        };
+
+        let color = if c.is_ascii_uppercase() { PieceColor::WHITE } else { PieceColor::BLACK };
+
+        Some(Piece::new(piece_type, color))
+    }
+
+    /// # Returns whether piece could make given move and whether path has to be clear
+    ///
+    /// kept for compatibility, see `move_kind`
     pub fn can_move_to(&self, m: Move, dest_occupied: bool) -> (bool, bool) {
+        match self.move_kind(m, dest_occupied) {
+            MoveKind::Illegal | MoveKind::Castle => (false, false),
+            MoveKind::Sliding | MoveKind::PawnDouble => (true, true),
+            _ => (true, false)
+        }
+    }
+
+    /// # Returns kind of given move based on piece's movement rules
+    ///
+    /// only geometry is checked, board is responsible for clearing paths, castling rules
+    /// and en passant, which piece never reports
+    ///
+    /// ```
+    /// use chess_api::movement::Move;
+    /// use chess_api::piece::{Piece, PieceType, PieceColor, MoveKind};
+    ///
+    /// let pawn = Piece::new(PieceType::Pawn, PieceColor::WHITE);
+    ///
+    /// assert_eq!(pawn.move_kind(Move::from_uci("e2e3").unwrap(), false), MoveKind::Normal);
+    /// assert_eq!(pawn.move_kind(Move::from_uci("e2e4").unwrap(), false), MoveKind::PawnDouble);
+    /// assert_eq!(pawn.move_kind(Move::from_uci("e7e8").unwrap(), false), MoveKind::Promotion);
+    /// assert_eq!(pawn.move_kind(Move::from_uci("e2f3").unwrap(), false), MoveKind::Illegal);
+    /// ```
+    pub fn move_kind(&self, m: Move, dest_occupied: bool) -> MoveKind {
         let (dx, dy) = m.to_deltas();
 
+        // piece can't stay on its own square
+        if dx == 0 && dy == 0 {
+            return MoveKind::Illegal;
+        }
+
+        let kind = |possible: bool, kind: MoveKind| if possible { kind } else { MoveKind::Illegal };
+
+        // knight jumps and king steps are looked up in precomputed tables
+        let reaches = || bitboard::targets(self.piece_type, self.piece_color, m.start().to_index()) & bitboard::bit(m.end().to_index()) != 0;
+
         match self.piece_type {
-            PieceType::Knight => ( (dx == 2 && dy == 1) || (dx == 1 && dy == 2), false ),
-            PieceType::Queen => ( dx == 0 || dy == 0 || dx == dy, true),
-            PieceType::King => ( dx <= 1 && dy <= 1, false ),
-            PieceType::Rook => ( dx == 0 || dy == 0, true ),
-            PieceType::Bishop => ( dx == dy, true ),
+            PieceType::Knight => kind(reaches(), MoveKind::Normal),
+            PieceType::Queen => kind(dx == 0 || dy == 0 || dx == dy, MoveKind::Sliding),
+            PieceType::King if dx == 2 && dy == 0 => kind(!self.moved, MoveKind::Castle),
+            PieceType::King => kind(reaches(), MoveKind::Normal),
+            PieceType::Rook => kind(dx == 0 || dy == 0, MoveKind::Sliding),
+            PieceType::Bishop => kind(dx == dy, MoveKind::Sliding),
             PieceType::Pawn => {
                 let ((sx, sy), (ex, ey)) = m.to_coords();
 
-                let distance = match self.piece_color {
-                    PieceColor::WHITE => {
-                        if ey > sy { 
-                            ey - sy
-                        } else {
-                            return (false, false);
-                        } 
-                    },
-                    PieceColor::BLACK => {
-                        if ey < sy {
-                            sy - ey
-                        } else {
-                            return (false, false);
-                        }
-                    }
+                let (forward, last_rank) = match self.piece_color {
+                    PieceColor::WHITE => (ey > sy, 7),
+                    PieceColor::BLACK => (ey < sy, 0)
                 };
 
+                if !forward {
+                    return MoveKind::Illegal;
+                }
+
+                let step = if ey == last_rank { MoveKind::Promotion } else { MoveKind::Normal };
 
                 if sx == ex && !dest_occupied {
-                    match distance {
-                        1 => (true, false),
-                        2 => (!self.moved, true),
-                        _ => (false, false)
+                    match dy {
+                        1 => step,
+                        2 => kind(!self.moved, MoveKind::PawnDouble),
+                        _ => MoveKind::Illegal
                     }
-                } else if dest_occupied && distance == 1 {
-                    if sx > ex {
-                        (sx - ex == 1, false) 
-                    } else if sx < ex {
-                        (ex - sx == 1, false) 
-                    } else { (false, false) }
-                } else { (false, false) } // todo en passant 
+                } else {
+                    kind(dest_occupied && dx == 1 && dy == 1, step)
+                }
             },
         }
     }
@@ -94,6 +202,32 @@ impl Piece {
         self.piece_color
     }
 
+    /// # Returns piece's FEN letter
+    ///
+    /// uppercase for white pieces, lowercase for black pieces
+    ///
+    /// ```
+    /// use chess_api::piece::{Piece, PieceType, PieceColor};
+    ///
+    /// assert_eq!(Piece::new(PieceType::Rook, PieceColor::WHITE).get_character(), 'R');
+    /// assert_eq!(Piece::new(PieceType::Knight, PieceColor::BLACK).get_character(), 'n');
+    /// ```
+    pub fn get_character(&self) -> char {
+        let c = match self.piece_type {
+            PieceType::Pawn => 'p',
+            PieceType::Knight => 'n',
+            PieceType::Bishop => 'b',
+            PieceType::Rook => 'r',
+            PieceType::Queen => 'q',
+            PieceType::King => 'k'
+        };
+
+        match self.piece_color {
+            PieceColor::WHITE => c.to_ascii_uppercase(),
+            PieceColor::BLACK => c
+        }
+    }
+
     pub fn piece_type(&self) -> PieceType {
         self.piece_type
     }
@@ -101,4 +235,17 @@ impl Piece {
     pub fn move_piece(&mut self) {
         self.moved = true;
     }    
+
+    /// # Changes piece's type, used when pawn reaches last rank
+    pub fn promote(&mut self, piece_type: PieceType) {
+        self.piece_type = piece_type;
+    }
+
+    pub fn reset_moved(&mut self) {
+        self.moved = false;
+    }
+
+    pub fn has_moved(&self) -> bool {
+        self.moved
+    }
 }
@@ -0,0 +1,191 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use super::board::Board;
+use super::movement::Square;
+use super::piece::*;
+
+
+impl Board {
+    /// # Returns board drawn with unicode chess glyphs
+    ///
+    /// board is drawn from white's perspective with rank numbers on the left
+    /// and file letters at the bottom, empty squares are drawn as dots,
+    /// requires `std` feature
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::new();
+    /// let rendered = board.render();
+    ///
+    /// assert_eq!(rendered.lines().next(), Some("8 ♜ ♞ ♝ ♛ ♚ ♝ ♞ ♜"));
+    /// assert_eq!(rendered.lines().last(), Some("  a b c d e f g h"));
+    /// ```
+    pub fn render(&self) -> String {
+        self.render_from(PieceColor::WHITE)
+    }
+
+    /// # Returns board drawn with unicode chess glyphs from given side
+    ///
+    /// from black's perspective rank 1 is at the top and files go from h to a
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let board = Board::new();
+    /// let rendered = board.render_from(PieceColor::BLACK);
+    ///
+    /// assert_eq!(rendered.lines().next(), Some("1 ♖ ♘ ♗ ♔ ♕ ♗ ♘ ♖"));
+    /// assert_eq!(rendered.lines().last(), Some("  h g f e d c b a"));
+    /// ```
+    pub fn render_from(&self, perspective: PieceColor) -> String {
+        self.render_with(perspective, piece_to_unicode)
+    }
+
+    /// # Returns board drawn with FEN piece letters
+    ///
+    /// same layout as `render` but uppercase letters are white pieces and lowercase letters are black pieces,
+    /// output is plain ASCII
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let rendered = Board::new().render_ascii();
+    ///
+    /// assert_eq!(rendered.lines().next(), Some("8 r n b q k b n r"));
+    /// assert!(rendered.is_ascii());
+    /// ```
+    pub fn render_ascii(&self) -> String {
+        self.render_with(PieceColor::WHITE, |piece| piece.get_character())
+    }
+
+    /// # Returns board drawn from given side with given piece glyphs
+    fn render_with(&self, perspective: PieceColor, glyph: fn(&Piece) -> char) -> String {
+        let (ranks, files) = perspective_order(perspective);
+        let mut rendered = String::new();
+
+        for y in ranks {
+            rendered.push_str(&(y + 1).to_string());
+
+            for x in files {
+                rendered.push(' ');
+                rendered.push(self.piece_at(x, y).map_or('.', glyph));
+            }
+
+            rendered.push('\n');
+        }
+
+        rendered.push(' ');
+
+        for x in files {
+            rendered.push(' ');
+            rendered.push((b'a' + x) as char);
+        }
+
+        rendered.push('\n');
+        rendered
+    }
+
+    /// # Returns board drawn with ANSI colors
+    ///
+    /// squares get alternating light and dark background and pieces get foreground color of their side,
+    /// only standard 8-color escape codes are used, use `render` for output without escape codes
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    ///
+    /// let board = Board::new();
+    /// let rendered = board.render_colored();
+    ///
+    /// assert!(rendered.contains("\x1b[42;37m ♖ "));
+    /// assert!(rendered.ends_with("   a  b  c  d  e  f  g  h \n"));
+    /// ```
+    pub fn render_colored(&self) -> String {
+        self.render_colored_from(PieceColor::WHITE)
+    }
+
+    /// # Returns board drawn with ANSI colors from given side
+    ///
+    /// see `render_colored` and `render_from`
+    ///
+    /// ```
+    /// # use chess_api::board::Board;
+    /// # use chess_api::piece::PieceColor;
+    ///
+    /// let board = Board::new();
+    /// let rendered = board.render_colored_from(PieceColor::BLACK);
+    ///
+    /// assert!(rendered.starts_with("1 \x1b[43;37m ♖ "));
+    /// assert!(rendered.ends_with("   h  g  f  e  d  c  b  a \n"));
+    /// ```
+    pub fn render_colored_from(&self, perspective: PieceColor) -> String {
+        let (ranks, files) = perspective_order(perspective);
+        let mut rendered = String::new();
+
+        for y in ranks {
+            rendered.push_str(&(y + 1).to_string());
+            rendered.push(' ');
+
+            for x in files {
+                let background = if Square::new(x, y).is_light() { LIGHT_SQUARE } else { DARK_SQUARE };
+
+                let (foreground, glyph) = match self.piece_at(x, y) {
+                    Some(piece) if piece.color() == PieceColor::WHITE => (WHITE_PIECE, piece_to_unicode(piece)),
+                    Some(piece) => (BLACK_PIECE, piece_to_unicode(piece)),
+                    None => (WHITE_PIECE, ' ')
+                };
+
+                rendered.push_str(&format!("\x1b[{};{}m {} ", background, foreground, glyph));
+            }
+
+            rendered.push_str("\x1b[0m\n");
+        }
+
+        rendered.push_str("  ");
+
+        for x in files {
+            rendered.push(' ');
+            rendered.push((b'a' + x) as char);
+            rendered.push(' ');
+        }
+
+        rendered.push('\n');
+        rendered
+    }
+}
+
+// ANSI color codes used by colored renderer
+const LIGHT_SQUARE: u8 = 43;
+const DARK_SQUARE: u8 = 42;
+const WHITE_PIECE: u8 = 37;
+const BLACK_PIECE: u8 = 30;
+
+/// # Returns ranks and files in drawing order, top to bottom and left to right
+fn perspective_order(perspective: PieceColor) -> ([u8; 8], [u8; 8]) {
+    let ascending = [0, 1, 2, 3, 4, 5, 6, 7];
+    let descending = [7, 6, 5, 4, 3, 2, 1, 0];
+
+    match perspective {
+        PieceColor::WHITE => (descending, ascending),
+        PieceColor::BLACK => (ascending, descending)
+    }
+}
+
+fn piece_to_unicode(piece: &Piece) -> char {
+    match (piece.color(), piece.piece_type()) {
+        (PieceColor::WHITE, PieceType::King) => '♔',
+        (PieceColor::WHITE, PieceType::Queen) => '♕',
+        (PieceColor::WHITE, PieceType::Rook) => '♖',
+        (PieceColor::WHITE, PieceType::Bishop) => '♗',
+        (PieceColor::WHITE, PieceType::Knight) => '♘',
+        (PieceColor::WHITE, PieceType::Pawn) => '♙',
+        (PieceColor::BLACK, PieceType::King) => '♚',
+        (PieceColor::BLACK, PieceType::Queen) => '♛',
+        (PieceColor::BLACK, PieceType::Rook) => '♜',
+        (PieceColor::BLACK, PieceType::Bishop) => '♝',
+        (PieceColor::BLACK, PieceType::Knight) => '♞',
+        (PieceColor::BLACK, PieceType::Pawn) => '♟'
+    }
+}
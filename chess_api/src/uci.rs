@@ -0,0 +1,139 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::board::*;
+use super::movement::*;
+
+
+/// # Reasons why UCI command could not be parsed
+#[derive(Debug, PartialEq)]
+pub enum UciError {
+    InvalidCommand(String), InvalidFen(FenError), InvalidMove(String)
+}
+
+/// # Search limits given by `go` command
+///
+/// times are in milliseconds, limits missing from command are None
+#[derive(Debug, PartialEq, Default)]
+pub struct GoCommand {
+    pub depth: Option<u8>,
+    pub movetime: Option<u64>,
+    pub wtime: Option<u64>,
+    pub btime: Option<u64>,
+    pub winc: Option<u64>,
+    pub binc: Option<u64>,
+    pub infinite: bool
+}
+
+/// # Parses `position` command into board
+///
+/// position is set up either from `startpos` or from `fen` followed by FEN fields,
+/// moves listed after `moves` are played in order
+///
+/// ```
+/// # use chess_api::uci::parse_position;
+/// # use chess_api::piece::PieceColor;
+///
+/// let board = parse_position("position startpos moves e2e4 e7e5").unwrap();
+///
+/// assert_eq!(board.turn(), PieceColor::WHITE);
+/// assert!(parse_position("position fen 4k3/8/8/8/8/8/8/4K3 w - - 0 1").is_ok());
+/// ```
+pub fn parse_position(command: &str) -> Result<Board, UciError> {
+    let invalid = || UciError::InvalidCommand(command.to_string());
+    let mut tokens = command.split_whitespace();
+
+    if tokens.next() != Some("position") {
+        return Err(invalid());
+    }
+
+    let board = match tokens.next() {
+        Some("startpos") => Board::new(),
+        Some("fen") => {
+            let fen: Vec<&str> = tokens.by_ref().take_while(|token| *token != "moves").collect();
+            let board = Board::from_fen(&fen.join(" ")).map_err(UciError::InvalidFen)?;
+
+            // moves keyword was consumed while collecting FEN fields
+            return play_moves(board, tokens);
+        },
+        _ => return Err(invalid())
+    };
+
+    match tokens.next() {
+        Some("moves") => play_moves(board, tokens),
+        Some(_) => Err(invalid()),
+        None => Ok(board)
+    }
+}
+
+/// # Parses `go` command into search limits
+///
+/// unknown options are ignored
+///
+/// ```
+/// # use chess_api::uci::parse_go;
+///
+/// let go = parse_go("go wtime 60000 btime 55000 depth 6").unwrap();
+///
+/// assert_eq!(go.depth, Some(6));
+/// assert_eq!(go.wtime, Some(60000));
+/// assert_eq!(go.movetime, None);
+/// ```
+pub fn parse_go(command: &str) -> Result<GoCommand, UciError> {
+    let invalid = || UciError::InvalidCommand(command.to_string());
+    let mut tokens = command.split_whitespace();
+    let mut go = GoCommand::default();
+
+    if tokens.next() != Some("go") {
+        return Err(invalid());
+    }
+
+    while let Some(token) = tokens.next() {
+        let limit = match token {
+            "depth" => {
+                go.depth = Some(tokens.next().and_then(|value| value.parse().ok()).ok_or_else(invalid)?);
+                continue;
+            },
+            "infinite" => {
+                go.infinite = true;
+                continue;
+            },
+            "movetime" => &mut go.movetime,
+            "wtime" => &mut go.wtime,
+            "btime" => &mut go.btime,
+            "winc" => &mut go.winc,
+            "binc" => &mut go.binc,
+            _ => continue
+        };
+
+        *limit = Some(tokens.next().and_then(|value| value.parse().ok()).ok_or_else(invalid)?);
+    }
+
+    Ok(go)
+}
+
+/// # Returns `bestmove` line for given move
+///
+/// missing move is written as null move `0000`, as required when there are no legal moves
+///
+/// ```
+/// # use chess_api::uci::format_bestmove;
+/// # use chess_api::movement::Move;
+///
+/// assert_eq!(format_bestmove(Move::from_uci("e7e8q").ok()), "bestmove e7e8q");
+/// assert_eq!(format_bestmove(None), "bestmove 0000");
+/// ```
+pub fn format_bestmove(m: Option<Move>) -> String {
+    format!("bestmove {}", m.map_or("0000".to_string(), |m| m.to_uci()))
+}
+
+/// # Plays moves written in uci notation on board
+fn play_moves<'a>(mut board: Board, moves: impl Iterator<Item = &'a str>) -> Result<Board, UciError> {
+    for token in moves {
+        let m = Move::from_uci(token).map_err(|_| UciError::InvalidMove(token.to_string()))?;
+        board.apply_move(m).map_err(|_| UciError::InvalidMove(token.to_string()))?;
+    }
+
+    Ok(board)
+}
@@ -0,0 +1,61 @@
+use crate::piece::{PieceType, PieceColor};
+
+const SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+const PIECE_KEYS: usize = 0;
+const SIDE_KEY: usize = PIECE_KEYS + 2 * 6 * 64;
+const CASTLING_KEYS: usize = SIDE_KEY + 1;
+const EN_PASSANT_KEYS: usize = CASTLING_KEYS + 4;
+const KEY_COUNT: usize = EN_PASSANT_KEYS + 8;
+
+/// # Random keys used for hashing positions
+///
+/// generated at compile time from fixed seed so hashes are stable across runs
+static KEYS: [u64; KEY_COUNT] = generate_keys();
+
+/// # Returns next splitmix64 state and its output
+const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = state;
+
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    (state, z ^ (z >> 31))
+}
+
+const fn generate_keys() -> [u64; KEY_COUNT] {
+    let mut keys = [0; KEY_COUNT];
+    let mut state = SEED;
+    let mut i = 0;
+
+    while i < KEY_COUNT {
+        let (next, key) = splitmix64(state);
+
+        keys[i] = key;
+        state = next;
+        i += 1;
+    }
+
+    keys
+}
+
+/// # Returns key of given piece standing on square with given index
+pub(crate) fn piece_key(piece_type: PieceType, color: PieceColor, index: usize) -> u64 {
+    KEYS[PIECE_KEYS + (color as usize * 6 + piece_type as usize) * 64 + index]
+}
+
+/// # Returns key xored in when black is to move
+pub(crate) fn side_key() -> u64 {
+    KEYS[SIDE_KEY]
+}
+
+/// # Returns key of castling right, in `KQkq` order
+pub(crate) fn castling_key(i: usize) -> u64 {
+    KEYS[CASTLING_KEYS + i]
+}
+
+/// # Returns key of en passant square's file
+pub(crate) fn en_passant_key(file: u8) -> u64 {
+    KEYS[EN_PASSANT_KEYS + file as usize]
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use chess_api::board::*;
 use chess_api::movement::*;
 use chess_api::piece::*;
@@ -147,7 +149,7 @@ use chess_api::piece::*;
 
     #[test]
     fn post_move_check() {
-        let mut board = Board::new_clear();
+        let mut board = Board::from_fen("8/8/8/8/8/8/8/8 b - - 0 1").unwrap();
 
         board.set(Square::new(1, 1), Some(Piece::new(PieceType::King, PieceColor::WHITE)));
         board.set(Square::new(5, 5), Some(Piece::new(PieceType::Rook, PieceColor::BLACK)));
@@ -157,7 +159,7 @@ use chess_api::piece::*;
 
     #[test]
     fn post_move_stelemate() {
-        let mut board = Board::new_clear();
+        let mut board = Board::from_fen("8/8/8/8/8/8/8/8 b - - 0 1").unwrap();
 
         board.set(Square::new(0, 1), Some(Piece::new(PieceType::King, PieceColor::WHITE)));
         board.set(Square::new(2, 1), Some(Piece::new(PieceType::Knight, PieceColor::BLACK)));
@@ -176,3 +178,1700 @@ use chess_api::piece::*;
 
         assert_eq!(board.perform_move(Move::new(Square::new(7, 1), Square::new(7, 0))).ok().unwrap(), PostMoveState::Checkmate);
     }
+
+    #[test]
+    fn from_fen_starting_position() {
+        let fen = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let board = Board::new();
+
+        for ((_, a), (_, b)) in fen.squares().zip(board.squares()) {
+            assert_eq!(a.map(|p| (p.piece_type(), p.color())), b.map(|p| (p.piece_type(), p.color())));
+        }
+
+        assert_eq!(fen.all_possible_moves(Some(PieceColor::WHITE)).count(), 20);
+    }
+
+    #[test]
+    fn from_fen_errors() {
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/8").err().unwrap(), FenError::WrongFieldCount(1));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8 w - - 0 1").err().unwrap(), FenError::WrongRankCount(7));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/7 w - - 0 1").err().unwrap(), FenError::WrongFileCount(7));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/ppppppppp w - - 0 1").err().unwrap(), FenError::WrongFileCount(9));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/4x3 w - - 0 1").err().unwrap(), FenError::InvalidPiece('x'));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/8 x - - 0 1").err().unwrap(), FenError::InvalidActiveColor);
+    }
+
+    #[test]
+    fn from_fen_malformed_rank() {
+        assert_eq!(Board::from_fen("99999999999999999999999999999/8/8/8/8/8/8/8 w - - 0 1").err().unwrap(), FenError::InvalidPiece('9'));
+        assert_eq!(Board::from_fen("88/8/8/8/8/8/8/8 w - - 0 1").err().unwrap(), FenError::WrongFileCount(16));
+        assert_eq!(Board::from_fen("7k1/8/8/8/8/8/8/8 w - - 0 1").err().unwrap(), FenError::WrongFileCount(9));
+        assert_eq!(Board::from_fen("08/8/8/8/8/8/8/8 w - - 0 1").err().unwrap(), FenError::InvalidPiece('0'));
+        assert_eq!(Board::from_fen("8/8/8/8/8/8/8/4K3 w K - 0 1").err().unwrap(), FenError::InvalidCastling('K'));
+    }
+
+    #[test]
+    fn from_fen_castling_availability() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Kq - 0 1").unwrap();
+
+        assert_eq!(board.get_piece(Square::new(4, 0)).unwrap().has_moved(), false);
+        assert_eq!(board.get_piece(Square::new(7, 0)).unwrap().has_moved(), false);
+        assert_eq!(board.get_piece(Square::new(0, 0)).unwrap().has_moved(), true);
+        assert_eq!(board.get_piece(Square::new(4, 7)).unwrap().has_moved(), false);
+        assert_eq!(board.get_piece(Square::new(0, 7)).unwrap().has_moved(), false);
+        assert_eq!(board.get_piece(Square::new(7, 7)).unwrap().has_moved(), true);
+    }
+
+    #[test]
+    fn fen_round_trip() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 b - - 0 1",
+            "r3k2r/8/8/8/8/8/8/R3K2R b Kq - 0 1",
+        ];
+
+        for fen in fens {
+            assert_eq!(Board::from_fen(fen).unwrap().to_fen(), fen);
+        }
+    }
+
+    #[test]
+    fn to_fen_after_moves() {
+        let mut board = Board::new();
+
+        assert!(board.perform_move(Move::new(Square::new(4, 1), Square::new(4, 3))).is_ok());
+        assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+
+        assert!(board.perform_move(Move::new(Square::new(4, 6), Square::new(4, 4))).is_ok());
+        assert!(board.perform_move(Move::new(Square::new(4, 0), Square::new(4, 1))).is_ok());
+        assert_eq!(board.to_fen(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPPKPPP/RNBQ1BNR b kq - 1 2");
+    }
+
+    #[test]
+    fn is_in_check() {
+        let board = Board::new();
+
+        assert_eq!(board.is_in_check(PieceColor::WHITE), false);
+        assert_eq!(board.is_in_check(PieceColor::BLACK), false);
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K2n w - - 0 1").unwrap();
+
+        assert_eq!(board.is_in_check(PieceColor::WHITE), false);
+
+        let board = Board::from_fen("4k3/8/8/8/8/3n4/8/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.is_in_check(PieceColor::WHITE), true);
+        assert_eq!(board.is_in_check(PieceColor::BLACK), false);
+    }
+
+    #[test]
+    fn is_in_check_ignores_friendly_pieces() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/5N2/4K2R w - - 0 1").unwrap();
+
+        assert_eq!(board.is_in_check(PieceColor::WHITE), false);
+    }
+
+    #[test]
+    fn is_in_check_without_king() {
+        let board = Board::from_fen("8/8/8/8/8/8/8/r7 w - - 0 1").unwrap();
+
+        assert_eq!(board.is_in_check(PieceColor::WHITE), false);
+    }
+
+    #[test]
+    fn is_in_check_by_pinned_piece() {
+        // black knight on e7 is pinned by the rook but still gives check
+        let board = Board::from_fen("4k3/4n3/8/3K4/8/8/8/4R3 w - - 0 1").unwrap();
+
+        assert_eq!(board.is_in_check(PieceColor::WHITE), true);
+        assert_eq!(board.check_move_possibility(Move::new(Square::new(3, 4), Square::new(2, 5))).err().unwrap(), MoveFailReason::KingAttacked);
+    }
+
+    #[test]
+    fn legal_moves() {
+        // white bishop is pinned, white king has to escape the knight's check
+        let board = Board::from_fen("4r2k/8/8/8/8/3n4/4B3/4K3 w - - 0 1").unwrap();
+        let moves = board.legal_moves(PieceColor::WHITE);
+
+        assert_eq!(moves.len(), 3);
+        assert!(moves.iter().all(|m| m.start() == Square::new(4, 0)));
+        assert!(moves.contains(&Move::new(Square::new(4, 0), Square::new(3, 0))));
+        assert!(moves.contains(&Move::new(Square::new(4, 0), Square::new(5, 0))));
+        assert!(moves.contains(&Move::new(Square::new(4, 0), Square::new(3, 1))));
+    }
+
+    #[test]
+    fn promotion() {
+        let mut board = Board::from_fen("7k/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+
+        assert!(board.perform_move(Move::from_uci("e7e8q").unwrap()).is_ok());
+        assert_eq!(board.get_piece(Square::new(4, 7)).unwrap().piece_type(), PieceType::Queen);
+        assert_eq!(board.get_piece(Square::new(4, 7)).unwrap().color(), PieceColor::WHITE);
+        assert_eq!(board.to_fen(), "4Q2k/8/8/8/8/8/8/4K3 b - - 0 1");
+
+        let mut board = Board::from_fen("7k/8/8/8/8/8/1p6/2R1K3 b - - 0 1").unwrap();
+
+        assert!(board.perform_move(Move::from_uci("b2c1n").unwrap()).is_ok());
+        assert_eq!(board.get_piece(Square::new(2, 0)).unwrap().piece_type(), PieceType::Knight);
+        assert_eq!(board.get_piece(Square::new(2, 0)).unwrap().color(), PieceColor::BLACK);
+    }
+
+    #[test]
+    fn promotion_requires_piece() {
+        let mut board = Board::from_fen("7k/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let m = Move::from_uci("e7e8").unwrap();
+
+        assert_eq!(board.check_move_possibility(m).err().unwrap(), MoveFailReason::IllegalPromotion);
+        assert_eq!(board.is_legal(m), false);
+        assert_eq!(board.is_move_possible(m), false);
+        assert!(board.legal_moves(PieceColor::WHITE).iter().all(|legal| legal.end() != m.end() || legal.promotion().is_some()));
+        assert!(board.apply_move(m).is_err());
+
+        assert!(board.perform_move(Move::from_uci("e7e8n").unwrap()).is_ok());
+        assert_eq!(board.get_piece(Square::new(4, 7)).unwrap().piece_type(), PieceType::Knight);
+    }
+
+    #[test]
+    fn err_illegal_promotion() {
+        let board = Board::from_fen("7k/4P3/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.check_move_possibility(Move::with_promotion(Square::new(4, 6), Square::new(4, 7), PieceType::King)).err().unwrap(), MoveFailReason::IllegalPromotion);
+        assert_eq!(board.check_move_possibility(Move::with_promotion(Square::new(4, 6), Square::new(4, 7), PieceType::Pawn)).err().unwrap(), MoveFailReason::IllegalPromotion);
+        assert_eq!(board.check_move_possibility(Move::with_promotion(Square::new(4, 1), Square::new(4, 2), PieceType::Queen)).err().unwrap(), MoveFailReason::IllegalPromotion);
+        assert_eq!(board.check_move_possibility(Move::with_promotion(Square::new(4, 0), Square::new(3, 0), PieceType::Queen)).err().unwrap(), MoveFailReason::IllegalPromotion);
+    }
+
+    #[test]
+    fn castling_in_standard_game() {
+        let mut board = Board::new();
+
+        // 1.e4 e5 2.Nf3 Nc6 3.Bc4 Bc5 4.O-O
+        for m in ["e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "f8c5", "e1g1"] {
+            assert!(board.perform_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.to_fen(), "r1bqk1nr/pppp1ppp/2n5/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQ1RK1 b kq - 5 4");
+    }
+
+    #[test]
+    fn castling_both_sides() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        assert!(board.check_move_possibility(Move::from_uci("e1g1").unwrap()).is_ok());
+        assert!(board.check_move_possibility(Move::from_uci("e1c1").unwrap()).is_ok());
+        assert!(board.check_move_possibility(Move::from_uci("e8g8").unwrap()).is_ok());
+        assert!(board.check_move_possibility(Move::from_uci("e8c8").unwrap()).is_ok());
+
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+
+        assert!(board.perform_move(Move::from_uci("e8c8").unwrap()).is_ok());
+        assert_eq!(board.to_fen(), "2kr3r/8/8/8/8/8/8/R3K2R w KQ - 1 2");
+    }
+
+    #[test]
+    fn castling_without_rights() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Qk - 0 1").unwrap();
+
+        assert_eq!(board.check_move_possibility(Move::from_uci("e1g1").unwrap()).err().unwrap(), MoveFailReason::IllegalMove);
+        assert!(board.check_move_possibility(Move::from_uci("e1c1").unwrap()).is_ok());
+        assert!(board.check_move_possibility(Move::from_uci("e8g8").unwrap()).is_ok());
+        assert_eq!(board.check_move_possibility(Move::from_uci("e8c8").unwrap()).err().unwrap(), MoveFailReason::IllegalMove);
+    }
+
+    #[test]
+    fn castling_blocked() {
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/RN2K1NR w KQkq - 0 1").unwrap();
+
+        assert_eq!(board.check_move_possibility(Move::from_uci("e1g1").unwrap()).err().unwrap(), MoveFailReason::IllegalMove);
+        assert_eq!(board.check_move_possibility(Move::from_uci("e1c1").unwrap()).err().unwrap(), MoveFailReason::IllegalMove);
+    }
+
+    #[test]
+    fn castling_through_check() {
+        // f1 is attacked by the rook on f5
+        let board = Board::from_fen("r3k2r/8/8/5r2/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        assert_eq!(board.check_move_possibility(Move::from_uci("e1g1").unwrap()).err().unwrap(), MoveFailReason::KingAttacked);
+        assert!(board.check_move_possibility(Move::from_uci("e1c1").unwrap()).is_ok());
+
+        // g1 is attacked by the rook on g5
+        let board = Board::from_fen("r3k2r/8/8/6r1/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        assert_eq!(board.check_move_possibility(Move::from_uci("e1g1").unwrap()).err().unwrap(), MoveFailReason::KingAttacked);
+
+        // king is in check
+        let board = Board::from_fen("r3k2r/8/8/4r3/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        assert_eq!(board.check_move_possibility(Move::from_uci("e1g1").unwrap()).err().unwrap(), MoveFailReason::KingAttacked);
+        assert_eq!(board.check_move_possibility(Move::from_uci("e1c1").unwrap()).err().unwrap(), MoveFailReason::KingAttacked);
+
+        // b1 may be attacked during queen side castling
+        let board = Board::from_fen("r3k2r/8/8/1r6/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        assert!(board.check_move_possibility(Move::from_uci("e1c1").unwrap()).is_ok());
+    }
+
+    #[test]
+    fn white_en_passant() {
+        let mut board = Board::new();
+
+        for m in ["e2e4", "a7a6", "e4e5", "d7d5"] {
+            assert!(board.perform_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.en_passant(), Some(Square::new(3, 5)));
+        assert!(board.perform_move(Move::from_uci("e5d6").unwrap()).is_ok());
+        assert_eq!(board.en_passant(), None);
+        assert_eq!(board.to_fen(), "rnbqkbnr/1pp1pppp/p2P4/8/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3");
+    }
+
+    #[test]
+    fn black_en_passant() {
+        let mut board = Board::new();
+
+        for m in ["a2a3", "d7d5", "a3a4", "d5d4", "e2e4"] {
+            assert!(board.perform_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.en_passant(), Some(Square::new(4, 2)));
+        assert!(board.perform_move(Move::from_uci("d4e3").unwrap()).is_ok());
+        assert_eq!(board.to_fen(), "rnbqkbnr/ppp1pppp/8/8/P7/4p3/1PPP1PPP/RNBQKBNR w KQkq - 0 4");
+    }
+
+    #[test]
+    fn en_passant_expires() {
+        let mut board = Board::new();
+
+        for m in ["e2e4", "a7a6", "e4e5", "d7d5", "h2h3", "h7h6"] {
+            assert!(board.perform_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.en_passant(), None);
+        assert_eq!(board.check_move_possibility(Move::from_uci("e5d6").unwrap()).err().unwrap(), MoveFailReason::IllegalMove);
+    }
+
+    #[test]
+    fn en_passant_exposing_king() {
+        // capturing en passant would remove both pawns from the fifth rank
+        let mut board = Board::from_fen("8/2p5/8/KP5r/8/8/8/7k b - - 0 1").unwrap();
+
+        assert!(board.perform_move(Move::from_uci("c7c5").unwrap()).is_ok());
+        assert_eq!(board.check_move_possibility(Move::from_uci("b5c6").unwrap()).err().unwrap(), MoveFailReason::KingAttacked);
+    }
+
+    #[test]
+    fn apply_move_captures() {
+        let mut board = Board::from_fen("4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1").unwrap();
+
+        let captured = board.apply_move(Move::from_uci("e4d5").unwrap()).unwrap().unwrap();
+
+        assert_eq!(captured.piece_type(), PieceType::Queen);
+        assert_eq!(captured.color(), PieceColor::BLACK);
+        assert_eq!(board.to_fen(), "4k3/8/8/3P4/8/8/8/4K3 b - - 0 1");
+    }
+
+    #[test]
+    fn apply_move_en_passant_capture() {
+        let mut board = Board::from_fen("4k3/3p4/8/4P3/8/8/8/4K3 b - - 0 1").unwrap();
+
+        assert!(board.apply_move(Move::from_uci("d7d5").unwrap()).unwrap().is_none());
+
+        let captured = board.apply_move(Move::from_uci("e5d6").unwrap()).unwrap().unwrap();
+
+        assert_eq!(captured.piece_type(), PieceType::Pawn);
+        assert_eq!(captured.color(), PieceColor::BLACK);
+        assert_eq!(board.to_fen(), "4k3/8/3P4/8/8/8/8/4K3 b - - 0 2");
+    }
+
+    #[test]
+    fn apply_illegal_move() {
+        let mut board = Board::new();
+
+        assert_eq!(board.apply_move(Move::from_uci("e2e5").unwrap()).err().unwrap(), MoveFailReason::IllegalMove);
+        assert_eq!(board.apply_move(Move::from_uci("e1g1").unwrap()).err().unwrap(), MoveFailReason::IllegalMove);
+        assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    }
+
+    #[test]
+    fn turn_order() {
+        let mut board = Board::new();
+
+        assert_eq!(board.turn(), PieceColor::WHITE);
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert_eq!(board.turn(), PieceColor::BLACK);
+
+        assert_eq!(board.apply_move(Move::from_uci("d2d4").unwrap()).err().unwrap(), MoveFailReason::WrongTurn);
+        assert_eq!(board.turn(), PieceColor::BLACK);
+        assert!(board.get_piece(Square::new(3, 1)).is_some());
+
+        assert!(board.apply_move(Move::from_uci("e7e5").unwrap()).is_ok());
+        assert_eq!(board.turn(), PieceColor::WHITE);
+        assert_eq!(board.apply_move(Move::from_uci("d7d5").unwrap()).err().unwrap(), MoveFailReason::WrongTurn);
+        assert!(board.apply_move(Move::from_uci("d2d4").unwrap()).is_ok());
+        assert_eq!(board.turn(), PieceColor::BLACK);
+    }
+
+    #[test]
+    fn undo_restores_position() {
+        let fen = "r3k2r/1P6/8/8/3p4/8/4P3/R3K2R w KQkq - 0 1";
+        let mut board = Board::from_fen(fen).unwrap();
+
+        // double push, en passant, castling both sides, capturing promotion
+        let moves = ["e2e4", "d4e3", "e1c1", "e8g8", "b7a8n", "f8f1", "d1f1"];
+
+        for (i, m) in moves.iter().enumerate() {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok(), "move {} failed", i);
+        }
+
+        assert_eq!(board.to_fen(), "N5k1/8/8/8/8/4p3/8/2K2R1R b - - 0 4");
+
+        for m in moves.iter().rev() {
+            assert_eq!(board.undo(), Some(Move::from_uci(m).unwrap()));
+        }
+
+        assert_eq!(board.undo(), None);
+        assert_eq!(board.to_fen(), fen);
+        assert_eq!(board.legal_moves(PieceColor::WHITE).len(), Board::from_fen(fen).unwrap().legal_moves(PieceColor::WHITE).len());
+    }
+
+    #[test]
+    fn undo_restores_en_passant() {
+        let mut board = Board::new();
+
+        for m in ["e2e4", "a7a6", "e4e5", "d7d5", "e5d6"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        board.undo();
+
+        assert_eq!(board.en_passant(), Some(Square::new(3, 5)));
+        assert_eq!(board.turn(), PieceColor::WHITE);
+        assert_eq!(board.get_piece(Square::new(3, 4)).unwrap().piece_type(), PieceType::Pawn);
+        assert!(board.get_piece(Square::new(3, 5)).is_none());
+    }
+
+    #[test]
+    fn is_legal() {
+        // rook pinned to its king by bishop
+        let board = Board::from_fen("6k1/8/8/8/8/2b5/3R4/4K3 w - - 0 1").unwrap();
+        let m = Move::from_uci("d2d5").unwrap();
+
+        assert_eq!(board.is_move_possible(m), true);
+        assert_eq!(board.is_legal(m), false);
+
+        assert_eq!(board.is_legal(Move::from_uci("e1f1").unwrap()), true);
+        assert_eq!(board.is_move_possible(Move::from_uci("c3d2").unwrap()), true);
+        assert_eq!(board.is_legal(Move::from_uci("c3d2").unwrap()), false);
+        assert_eq!(board.is_move_possible(Move::from_uci("d2e3").unwrap()), false);
+        assert_eq!(board.is_legal(Move::from_uci("d2e3").unwrap()), false);
+    }
+
+    #[test]
+    fn set_turn() {
+        let mut board = Board::new_clear();
+
+        board.set(Square::from_uci("e1").unwrap(), Some(Piece::new(PieceType::King, PieceColor::WHITE)));
+        board.set(Square::from_uci("e8").unwrap(), Some(Piece::new(PieceType::King, PieceColor::BLACK)));
+        board.set(Square::from_uci("a7").unwrap(), Some(Piece::new(PieceType::Pawn, PieceColor::BLACK)));
+        board.set_turn(PieceColor::BLACK);
+
+        let moves = board.legal_moves(board.turn());
+
+        assert_eq!(moves.len(), 7);
+        assert!(moves.iter().all(|m| board.get_piece(m.start()).unwrap().color() == PieceColor::BLACK));
+        assert!(board.apply_move(Move::from_uci("a7a5").unwrap()).is_ok());
+        assert_eq!(board.turn(), PieceColor::WHITE);
+    }
+
+    #[test]
+    fn move_to_san() {
+        let mut board = Board::new();
+
+        let moves = [
+            ("e2e4", "e4"), ("d7d5", "d5"), ("e4d5", "exd5"), ("g8f6", "Nf6"), ("f1b5", "Bb5+"),
+            ("c7c6", "c6"), ("d5c6", "dxc6"), ("b8d7", "Nbd7"), ("g1f3", "Nf3"), ("d8c7", "Qc7"),
+            ("e1g1", "O-O"), ("e7e5", "e5"), ("c6b7", "cxb7"), ("f8e7", "Be7"), ("b7a8r", "bxa8=R"),
+        ];
+
+        for (uci, san) in moves {
+            let m = Move::from_uci(uci).unwrap();
+
+            assert_eq!(board.move_to_san(m), san);
+            assert!(board.apply_move(m).is_ok());
+        }
+    }
+
+    #[test]
+    fn move_to_san_disambiguation() {
+        // knights sharing a file are told apart by rank
+        let board = Board::from_fen("4k3/8/8/6N1/8/8/8/4K1N1 w - - 0 1").unwrap();
+
+        assert_eq!(board.move_to_san(Move::from_uci("g1f3").unwrap()), "N1f3");
+        assert_eq!(board.move_to_san(Move::from_uci("g5f3").unwrap()), "N5f3");
+
+        // knights sharing a rank are told apart by file
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1").unwrap();
+
+        assert_eq!(board.move_to_san(Move::from_uci("b1d2").unwrap()), "Nbd2");
+        assert_eq!(board.move_to_san(Move::from_uci("f1d2").unwrap()), "Nfd2");
+
+        // file is preferred when it is enough, even if rank would be enough too
+        let board = Board::from_fen("4k3/8/8/8/5N2/2N5/8/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.move_to_san(Move::from_uci("c3e2").unwrap()), "Nce2");
+        assert_eq!(board.move_to_san(Move::from_uci("f4d5").unwrap()), "Nfd5");
+        assert_eq!(board.move_to_san(Move::from_uci("c3e4").unwrap()), "Ne4");
+
+        // queen sharing a file with one queen and a rank with another needs full square
+        let board = Board::from_fen("8/2k5/8/8/4Q2Q/8/K7/7Q w - - 0 1").unwrap();
+
+        assert_eq!(board.move_to_san(Move::from_uci("h4e1").unwrap()), "Qh4e1");
+        assert_eq!(board.move_to_san(Move::from_uci("e4e1").unwrap()), "Qee1");
+        assert_eq!(board.move_to_san(Move::from_uci("h1e1").unwrap()), "Q1e1");
+
+        for san in ["Qh4e1", "Qee1", "Q1e1"] {
+            assert!(board.san_to_move(san).is_ok());
+        }
+
+        // pinned knight can not reach the square, so no disambiguation is needed
+        let board = Board::from_fen("4r2k/8/8/8/8/8/2N1N3/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.move_to_san(Move::from_uci("c2d4").unwrap()), "Nd4");
+    }
+
+    #[test]
+    fn move_to_san_special_moves() {
+        let board = Board::from_fen("r3k3/1P6/8/3pP3/8/8/8/4K2R w Kq - 0 1").unwrap();
+
+        assert_eq!(board.move_to_san(Move::from_uci("e1g1").unwrap()), "O-O");
+        assert_eq!(board.move_to_san(Move::from_uci("b7b8q").unwrap()), "b8=Q+");
+        assert_eq!(board.move_to_san(Move::from_uci("b7a8n").unwrap()), "bxa8=N");
+    }
+
+    #[test]
+    fn move_to_san_checkmate() {
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R3K3 w Q - 0 1").unwrap();
+
+        assert_eq!(board.move_to_san(Move::from_uci("a1a8").unwrap()), "Ra8#");
+        assert_eq!(board.move_to_san(Move::from_uci("e1c1").unwrap()), "O-O-O");
+
+        // en passant
+        let mut board = Board::new();
+
+        for m in ["e2e4", "a7a6", "e4e5", "d7d5"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.move_to_san(Move::from_uci("e5d6").unwrap()), "exd6");
+    }
+
+    #[test]
+    fn san_to_move() {
+        let board = Board::from_fen("r3k2r/1P6/8/3pP3/8/8/8/RN2KN1R w KQkq d6 0 1").unwrap();
+
+        assert_eq!(board.san_to_move("Nbd2"), Ok(Move::from_uci("b1d2").unwrap()));
+        assert_eq!(board.san_to_move("Nd2"), Err(SanError::AmbiguousMove));
+        assert_eq!(board.san_to_move("Nfd2"), Ok(Move::from_uci("f1d2").unwrap()));
+        assert_eq!(board.san_to_move("bxa8=N+"), Ok(Move::from_uci("b7a8n").unwrap()));
+        assert_eq!(board.san_to_move("bxa8Q"), Ok(Move::from_uci("b7a8q").unwrap()));
+        assert_eq!(board.san_to_move("b8"), Err(SanError::IllegalMove));
+        assert_eq!(board.san_to_move("O-O"), Err(SanError::IllegalMove));
+        assert_eq!(board.san_to_move("O-O-O"), Err(SanError::IllegalMove));
+        assert_eq!(board.san_to_move("Nc3"), Ok(Move::from_uci("b1c3").unwrap()));
+        assert_eq!(board.san_to_move("Kd1"), Ok(Move::from_uci("e1d1").unwrap()));
+        assert_eq!(board.san_to_move("Xe4"), Err(SanError::InvalidFormat));
+        assert_eq!(board.san_to_move("e"), Err(SanError::InvalidFormat));
+        assert_eq!(board.san_to_move("Nb1d2d"), Err(SanError::InvalidFormat));
+
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+
+        assert_eq!(board.san_to_move("O-O-O"), Ok(Move::from_uci("e8c8").unwrap()));
+        assert_eq!(board.san_to_move("O-O"), Ok(Move::from_uci("e8g8").unwrap()));
+    }
+
+    #[test]
+    fn parse_long_algebraic() {
+        let mut board = Board::from_fen("r3k3/1P1p4/8/4P3/8/8/8/4K2R b Kq - 0 1").unwrap();
+
+        assert!(board.apply_move(Move::from_uci("d7d5").unwrap()).is_ok());
+        assert_eq!(board.parse_long_algebraic("e5xd6"), Ok(Move::from_uci("e5d6").unwrap()));
+        assert_eq!(board.parse_long_algebraic("e5-d6"), Ok(Move::from_uci("e5d6").unwrap()));
+        assert_eq!(board.parse_long_algebraic("b7xa8=N"), Ok(Move::from_uci("b7a8n").unwrap()));
+        assert_eq!(board.parse_long_algebraic("b7-b8=Q+"), Ok(Move::from_uci("b7b8q").unwrap()));
+        assert_eq!(board.parse_long_algebraic("b7b8r"), Ok(Move::from_uci("b7b8r").unwrap()));
+        assert_eq!(board.parse_long_algebraic("Rh1-g2"), Err(LanError::IllegalMove));
+        assert_eq!(board.parse_long_algebraic("Rh1-h7"), Ok(Move::from_uci("h1h7").unwrap()));
+        assert_eq!(board.parse_long_algebraic("Ke1-g1"), Ok(Move::from_uci("e1g1").unwrap()));
+        assert_eq!(board.parse_long_algebraic("O-O"), Ok(Move::from_uci("e1g1").unwrap()));
+
+        // moves of wrong piece or side
+        assert_eq!(board.parse_long_algebraic("Nh1-h7"), Err(LanError::IllegalMove));
+        assert_eq!(board.parse_long_algebraic("d5-d4"), Err(LanError::IllegalMove));
+        assert_eq!(board.parse_long_algebraic("b7-b8"), Err(LanError::IllegalMove));
+
+        for lan in ["", "e2", "e2-", "e2-e9", "b7-b8=", "b7-b8=K", "Xe2-e4", "e2--e4"] {
+            assert_eq!(board.parse_long_algebraic(lan), Err(LanError::InvalidFormat));
+        }
+    }
+
+    #[test]
+    fn san_round_trip() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        ];
+
+        for fen in fens {
+            let board = Board::from_fen(fen).unwrap();
+
+            for m in board.legal_moves(board.turn()) {
+                assert_eq!(board.san_to_move(&board.move_to_san(m)), Ok(m));
+            }
+        }
+    }
+
+    #[test]
+    fn square_attacked_by_pawn() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.is_square_attacked(Square::new(4, 2), PieceColor::WHITE), false);
+        assert_eq!(board.is_square_attacked(Square::new(4, 3), PieceColor::WHITE), false);
+        assert_eq!(board.is_square_attacked(Square::new(3, 2), PieceColor::WHITE), true);
+        assert_eq!(board.is_square_attacked(Square::new(5, 2), PieceColor::WHITE), true);
+        assert_eq!(board.is_square_attacked(Square::new(3, 0), PieceColor::WHITE), true); // by king
+
+        let board = Board::from_fen("4k3/4p3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.is_square_attacked(Square::new(4, 5), PieceColor::BLACK), false);
+        assert_eq!(board.is_square_attacked(Square::new(3, 5), PieceColor::BLACK), true);
+        assert_eq!(board.is_square_attacked(Square::new(5, 5), PieceColor::BLACK), true);
+        assert_eq!(board.is_square_attacked(Square::new(3, 7), PieceColor::BLACK), true); // by king
+        assert_eq!(board.is_square_attacked(Square::new(3, 7), PieceColor::WHITE), false);
+    }
+
+    #[test]
+    fn castling_through_pawn_attack() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/6p1/R3K2R w KQ - 0 1").unwrap();
+
+        assert_eq!(board.check_move_possibility(Move::from_uci("e1g1").unwrap()).err().unwrap(), MoveFailReason::KingAttacked);
+        assert!(board.check_move_possibility(Move::from_uci("e1c1").unwrap()).is_ok());
+    }
+
+    #[test]
+    fn piece_at() {
+        let board = Board::new();
+
+        for y in 0..8 {
+            for x in 0..8 {
+                assert_eq!(board.piece_at(x, y).map(|p| p.piece_type()), board.get_piece(Square::new(x, y)).map(|p| p.piece_type()));
+            }
+        }
+
+        assert!(board.piece_at(0, 8).is_none());
+        assert!(board.piece_at(8, 8).is_none());
+        assert!(board.piece_at(u8::MAX, 0).is_none());
+    }
+
+    #[test]
+    fn cloned_board_is_independent() {
+        let mut board = Board::new();
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+
+        let mut clone = board.clone();
+
+        assert!(clone.apply_move(Move::from_uci("e7e5").unwrap()).is_ok());
+        assert!(clone.apply_move(Move::from_uci("e1e2").unwrap()).is_ok());
+
+        assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        assert_eq!(board.turn(), PieceColor::BLACK);
+        assert_eq!(board.en_passant(), Some(Square::new(4, 2)));
+
+        assert_eq!(clone.undo(), Some(Move::from_uci("e1e2").unwrap()));
+        assert_eq!(clone.undo(), Some(Move::from_uci("e7e5").unwrap()));
+        assert_eq!(clone.undo(), Some(Move::from_uci("e2e4").unwrap()));
+        assert_eq!(board.undo(), Some(Move::from_uci("e2e4").unwrap()));
+        assert_eq!(board.undo(), None);
+    }
+
+    #[test]
+    fn board_equality() {
+        assert!(Board::new() == Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap());
+        assert!(Board::new() != Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1").unwrap());
+        assert!(Board::new() != Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w Kkq - 0 1").unwrap());
+        assert!(Board::new() != Board::new_clear());
+
+        // knights going back and forth reach the same position
+        let mut board = Board::new();
+
+        for m in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert!(board == Board::new());
+
+        // rook going back and forth loses castling availability
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        for m in ["h1h2", "h8h7", "h2h1", "h7h8"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert!(board != Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap());
+        assert!(board == Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Qq - 0 1").unwrap());
+    }
+
+    #[test]
+    fn board_equality_en_passant() {
+        let mut a = Board::new();
+        let mut b = Board::new();
+
+        for m in ["e2e4", "e7e5"] {
+            assert!(a.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        for m in ["e2e3", "e7e6", "e3e4", "e6e5"] {
+            assert!(b.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert!(a != b);
+
+        for m in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+            assert!(a.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert!(a == b);
+    }
+
+    #[test]
+    fn threefold_repetition() {
+        let mut board = Board::new();
+
+        for _ in 0..2 {
+            for m in ["b1c3", "b8c6", "c3b1", "c6b8"] {
+                assert_eq!(board.is_threefold_repetition(), false);
+                assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+            }
+        }
+
+        assert_eq!(board.is_threefold_repetition(), true);
+
+        board.undo();
+
+        assert_eq!(board.is_threefold_repetition(), false);
+    }
+
+    #[test]
+    fn threefold_repetition_castling_rights() {
+        // first position still had castling rights so it differs from later ones
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        for m in ["e1f1", "e8f8", "f1e1", "f8e8", "e1f1", "e8f8", "f1e1", "f8e8"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.is_threefold_repetition(), false);
+
+        for m in ["e1f1", "e8f8", "f1e1", "f8e8"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.is_threefold_repetition(), true);
+    }
+
+    #[test]
+    fn threefold_repetition_en_passant() {
+        // en passant was possible only the first time
+        let mut board = Board::from_fen("4k3/3p4/8/4P3/8/8/8/4K3 b - - 0 1").unwrap();
+
+        for m in ["d7d5", "e1e2", "e8e7", "e2e1", "e7e8", "e1e2", "e8e7", "e2e1", "e7e8"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.is_threefold_repetition(), false);
+
+        for m in ["e1e2", "e8e7", "e2e1", "e7e8"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.is_threefold_repetition(), true);
+    }
+
+    #[test]
+    fn halfmove_clock() {
+        let mut board = Board::from_fen("4k3/8/8/3r4/8/8/8/3RK3 w - - 10 30").unwrap();
+
+        assert_eq!(board.halfmove_clock(), 10);
+        assert!(board.apply_move(Move::from_uci("e1e2").unwrap()).is_ok());
+        assert_eq!(board.halfmove_clock(), 11);
+        assert_eq!(board.to_fen(), "4k3/8/8/3r4/8/8/4K3/3R4 b - - 11 30");
+
+        assert!(board.apply_move(Move::from_uci("d5d1").unwrap()).is_ok());
+        assert_eq!(board.halfmove_clock(), 0);
+
+        board.undo();
+
+        assert_eq!(board.halfmove_clock(), 11);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - x 1").err().unwrap(), FenError::InvalidHalfmoveClock);
+    }
+
+    #[test]
+    fn castling_rights() {
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        assert_eq!(board.castling_rights(), CastlingRights::ALL);
+        assert!(board.apply_move(Move::from_uci("a1a2").unwrap()).is_ok());
+
+        let rights = board.castling_rights();
+
+        assert_eq!(rights.has(PieceColor::WHITE, false), false);
+        assert_eq!(rights.has(PieceColor::WHITE, true), true);
+        assert_eq!(rights, CastlingRights::WHITE_KINGSIDE | CastlingRights::BLACK_KINGSIDE | CastlingRights::BLACK_QUEENSIDE);
+        assert_eq!(board.to_fen(), "r3k2r/8/8/8/8/8/R7/4K2R b Kkq - 1 1");
+
+        // moving the rook back doesn't restore the right
+        assert!(board.apply_move(Move::from_uci("e8d8").unwrap()).is_ok());
+        assert!(board.apply_move(Move::from_uci("a2a1").unwrap()).is_ok());
+        assert_eq!(board.castling_rights(), CastlingRights::WHITE_KINGSIDE);
+
+        board.undo();
+        board.undo();
+        board.undo();
+        assert_eq!(board.castling_rights(), CastlingRights::ALL);
+
+        assert_eq!(Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Kq - 0 1").unwrap().castling_rights(), CastlingRights::WHITE_KINGSIDE | CastlingRights::BLACK_QUEENSIDE);
+        assert!(Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1").unwrap().castling_rights().is_empty());
+    }
+
+    #[test]
+    fn castling_rights_lost_by_rook_capture() {
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/6b1/R3K2R b KQkq - 0 1").unwrap();
+
+        assert!(board.apply_move(Move::from_uci("g2h1").unwrap()).is_ok());
+        assert_eq!(board.castling_rights().has(PieceColor::WHITE, true), false);
+        assert_eq!(board.castling_rights(), CastlingRights::WHITE_QUEENSIDE | CastlingRights::BLACK_KINGSIDE | CastlingRights::BLACK_QUEENSIDE);
+        assert_eq!(board.to_fen(), "r3k2r/8/8/8/8/8/8/R3K2b w Qkq - 0 2");
+        assert!(board.san_to_move("O-O").is_err());
+
+        // capturing rook on its home square with a rook takes both rights
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        assert!(board.apply_move(Move::from_uci("h1h8").unwrap()).is_ok());
+        assert_eq!(board.castling_rights(), CastlingRights::WHITE_QUEENSIDE | CastlingRights::BLACK_QUEENSIDE);
+
+        board.undo();
+        assert_eq!(board.castling_rights(), CastlingRights::ALL);
+
+        // Chess960 rook captured away from the corner
+        let mut board = Board::from_fen("1r4kr/8/8/8/8/8/8/1R4KR b BHbh - 0 1").unwrap();
+
+        assert!(board.apply_move(Move::from_uci("b8b1").unwrap()).is_ok());
+        assert_eq!(board.castling_rights(), CastlingRights::WHITE_KINGSIDE | CastlingRights::BLACK_KINGSIDE);
+    }
+
+    #[test]
+    fn fen_en_passant() {
+        let mut board = Board::new();
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+
+        let fen = "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3";
+        let board = Board::from_fen(fen).unwrap();
+
+        assert_eq!(board.en_passant(), Some(Square::from_uci("d6").unwrap()));
+        assert_eq!(board.to_fen(), fen);
+        assert_eq!(board.move_to_san(Move::from_uci("e5d6").unwrap()), "exd6");
+
+        for en_passant in ["d5", "c6", "d3", "x9"] {
+            let fen = format!("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq {} 0 3", en_passant);
+            assert_eq!(Board::from_fen(&fen).err(), Some(FenError::InvalidEnPassant));
+        }
+    }
+
+    #[test]
+    fn fullmove_number() {
+        let mut board = Board::new();
+
+        assert_eq!(board.fullmove_number(), 1);
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert_eq!(board.fullmove_number(), 1);
+        assert!(board.apply_move(Move::from_uci("e7e5").unwrap()).is_ok());
+        assert_eq!(board.fullmove_number(), 2);
+        assert_eq!(board.to_fen(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2");
+
+        board.undo();
+        assert_eq!(board.fullmove_number(), 1);
+
+        let fen = "4k3/8/8/8/8/8/8/4K3 b - - 12 42";
+        let mut board = Board::from_fen(fen).unwrap();
+
+        assert_eq!(board.to_fen(), fen);
+        assert!(board.apply_move(Move::from_uci("e8e7").unwrap()).is_ok());
+        assert_eq!(board.fullmove_number(), 43);
+
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 x").err().unwrap(), FenError::InvalidFullmoveNumber);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 0").err().unwrap(), FenError::InvalidFullmoveNumber);
+    }
+
+    #[test]
+    fn move_counters_saturate() {
+        let fen = "4k3/8/8/8/8/8/8/4K3 b - - 4294967295 4294967295";
+        let mut board = Board::from_fen(fen).unwrap();
+
+        assert!(board.apply_move(Move::from_uci("e8e7").unwrap()).is_ok());
+        assert_eq!(board.halfmove_clock(), u32::MAX);
+        assert_eq!(board.fullmove_number(), u32::MAX);
+
+        board.undo();
+        assert_eq!(board.to_fen(), fen);
+    }
+
+    #[test]
+    fn fifty_move_draw() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 96 80").unwrap();
+
+        for m in ["e1e2", "e8e7", "e2e1"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+            assert_eq!(board.is_fifty_move_draw(), false);
+        }
+
+        assert!(board.apply_move(Move::from_uci("e7e8").unwrap()).is_ok());
+        assert_eq!(board.is_fifty_move_draw(), true);
+    }
+
+    #[test]
+    fn insufficient_material() {
+        // king against king
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap().is_insufficient_material(), true);
+        // king and bishop against king
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap().is_insufficient_material(), true);
+        // king and knight against king
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/1n2K3 w - - 0 1").unwrap().is_insufficient_material(), true);
+        // bishops on squares of the same color
+        assert_eq!(Board::from_fen("2b1k3/8/8/8/8/8/8/4KB2 w - - 0 1").unwrap().is_insufficient_material(), true);
+        // bishops on squares of different colors
+        assert_eq!(Board::from_fen("2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1").unwrap().is_insufficient_material(), false);
+        // lone pawn
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap().is_insufficient_material(), false);
+        // two knights
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/1N2K1N1 w - - 0 1").unwrap().is_insufficient_material(), false);
+        assert_eq!(Board::new().is_insufficient_material(), false);
+    }
+
+    #[test]
+    fn zobrist_hash() {
+        let mut board = Board::new();
+
+        // equal boards reached by different move orders
+        for m in ["e2e3", "e7e6", "g1f3"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        let mut other = Board::new();
+
+        for m in ["g1f3", "e7e6", "e2e3"] {
+            assert!(other.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert!(board == other);
+        assert_eq!(board.zobrist_hash(), other.zobrist_hash());
+        assert_eq!(board.zobrist_hash(), Board::from_fen(&board.to_fen()).unwrap().zobrist_hash());
+
+        // side to move, castling and en passant change hash
+        let fens = [
+            "r3k2r/8/8/8/4P3/8/8/R3K2R w KQkq - 0 1",
+            "r3k2r/8/8/8/4P3/8/8/R3K2R b KQkq - 0 1",
+            "r3k2r/8/8/8/4P3/8/8/R3K2R w Qkq - 0 1",
+            "r3k2r/8/8/8/4P3/8/8/R3K2R w - - 0 1",
+            "r3k2r/8/8/8/4P3/8/8/R3K2R w KQkq - 0 1",
+        ];
+
+        let hashes: Vec<u64> = fens.iter().map(|fen| Board::from_fen(fen).unwrap().zobrist_hash()).collect();
+
+        assert_ne!(hashes[0], hashes[1]);
+        assert_ne!(hashes[0], hashes[2]);
+        assert_ne!(hashes[2], hashes[3]);
+        assert_eq!(hashes[0], hashes[4]);
+
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+        let mut other = Board::from_fen("4k3/8/8/8/8/4P3/8/4K3 w - - 0 1").unwrap();
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert!(other.apply_move(Move::from_uci("e3e4").unwrap()).is_ok());
+
+        assert!(board != other);
+        assert_ne!(board.zobrist_hash(), other.zobrist_hash());
+    }
+
+    #[test]
+    fn legal_moves_iter() {
+        let mut board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+
+        assert!(board.apply_move(Move::from_uci("a2a4").unwrap()).is_ok());
+
+        for color in [PieceColor::WHITE, PieceColor::BLACK] {
+            let mut lazy: Vec<Move> = board.legal_moves_iter(color).collect();
+            let mut collected: Vec<Move> = board.all_possible_moves(Some(color)).collect();
+
+            lazy.sort_by_key(|m| m.to_uci());
+            collected.sort_by_key(|m| m.to_uci());
+
+            assert_eq!(lazy, collected);
+        }
+
+        // captures come before quiet moves, b4a3 is en passant
+        let moves: Vec<Move> = board.legal_moves_iter(PieceColor::BLACK).collect();
+        let captures = moves.iter().take_while(|m| board.get_piece(m.end()).is_some() || m.end() == Square::from_uci("a3").unwrap()).count();
+
+        assert!(moves.contains(&Move::from_uci("b4a3").unwrap()));
+        assert_eq!(moves[captures..].iter().filter(|m| board.get_piece(m.end()).is_some()).count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn best_move_timed() {
+        use std::time::{Duration, Instant};
+
+        let board = Board::from_fen("r1bq1rk1/pp2bppp/2n1pn2/3p4/2PP4/2N1PN2/PP3PPP/R2QKB1R w KQ - 0 1").unwrap();
+        let budget = Duration::from_millis(300);
+        let start = Instant::now();
+
+        let m = board.best_move_timed(budget).unwrap();
+
+        assert!(start.elapsed() < budget * 3);
+        assert!(board.legal_moves(PieceColor::WHITE).contains(&m));
+    }
+
+    #[test]
+    fn board_builder() {
+        let e1 = Square::from_uci("e1").unwrap();
+        let e8 = Square::from_uci("e8").unwrap();
+
+        let board = BoardBuilder::new()
+            .piece(e1, PieceType::King, PieceColor::WHITE)
+            .piece(e8, PieceType::King, PieceColor::BLACK)
+            .piece(Square::from_uci("a8").unwrap(), PieceType::Rook, PieceColor::BLACK)
+            .piece(Square::from_uci("d4").unwrap(), PieceType::Pawn, PieceColor::WHITE)
+            .piece(Square::from_uci("e4").unwrap(), PieceType::Pawn, PieceColor::BLACK)
+            .turn(PieceColor::BLACK)
+            .castling(PieceColor::BLACK, false)
+            .en_passant(Square::from_uci("d3").unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(board.turn(), PieceColor::BLACK);
+        assert_eq!(board.en_passant(), Some(Square::from_uci("d3").unwrap()));
+        assert!(board.legal_moves(PieceColor::BLACK).contains(&Move::from_uci("e4d3").unwrap()));
+        assert!(board.legal_moves(PieceColor::BLACK).contains(&Move::from_uci("e8c8").unwrap()));
+
+        let two_kings = BoardBuilder::new()
+            .piece(e1, PieceType::King, PieceColor::WHITE)
+            .piece(Square::from_uci("a1").unwrap(), PieceType::King, PieceColor::WHITE)
+            .piece(e8, PieceType::King, PieceColor::BLACK)
+            .build();
+
+        assert_eq!(two_kings.err(), Some(BoardError::TooManyKings(PieceColor::WHITE)));
+
+        let no_black_king = BoardBuilder::new().piece(e1, PieceType::King, PieceColor::WHITE).build();
+        assert_eq!(no_black_king.err(), Some(BoardError::MissingKing(PieceColor::BLACK)));
+
+        let no_rook = BoardBuilder::new()
+            .piece(e1, PieceType::King, PieceColor::WHITE)
+            .piece(e8, PieceType::King, PieceColor::BLACK)
+            .castling(PieceColor::WHITE, true)
+            .build();
+
+        assert_eq!(no_rook.err(), Some(BoardError::InvalidCastling(PieceColor::WHITE, true)));
+
+        let no_pawn = BoardBuilder::new()
+            .piece(e1, PieceType::King, PieceColor::WHITE)
+            .piece(e8, PieceType::King, PieceColor::BLACK)
+            .en_passant(Square::from_uci("d6").unwrap())
+            .build();
+
+        assert_eq!(no_pawn.err(), Some(BoardError::InvalidEnPassant(Square::from_uci("d6").unwrap())));
+    }
+
+    #[test]
+    fn validate() {
+        assert_eq!(Board::new().validate(), Ok(()));
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/8 w - - 0 1").unwrap();
+        assert_eq!(board.validate(), Err(vec![PositionError::MissingKing(PieceColor::WHITE)]));
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/K3K3 w - - 0 1").unwrap();
+        assert_eq!(board.validate(), Err(vec![PositionError::TooManyKings(PieceColor::WHITE)]));
+
+        let board = Board::from_fen("P3k3/8/8/8/8/8/8/4K2p w - - 0 1").unwrap();
+        assert_eq!(board.validate(), Err(vec![
+            PositionError::PawnOnBackRank(Square::from_uci("h1").unwrap()),
+            PositionError::PawnOnBackRank(Square::from_uci("a8").unwrap())
+        ]));
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4KR2 w - - 0 1").unwrap();
+        assert_eq!(board.validate(), Ok(()));
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1").unwrap();
+        assert_eq!(board.validate(), Err(vec![PositionError::OpponentInCheck]));
+
+        // en passant square has to lie behind the pawn which just made double push
+        let mut board = Board::new();
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert_eq!(board.validate(), Ok(()));
+
+        board.set(Square::from_uci("e4").unwrap(), None);
+        assert_eq!(board.validate(), Err(vec![PositionError::InvalidEnPassant(Square::from_uci("e3").unwrap())]));
+    }
+
+    #[test]
+    fn check_follows_board_changes() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let e4 = Square::from_uci("e4").unwrap();
+
+        assert_eq!(board.is_in_check(PieceColor::BLACK), false);
+
+        board.set(Square::from_uci("a1").unwrap(), Some(Piece::new(PieceType::Rook, PieceColor::WHITE)));
+        assert!(board.apply_move(Move::from_uci("a1a8").unwrap()).is_ok());
+        assert_eq!(board.is_in_check(PieceColor::BLACK), true);
+
+        board.undo();
+        assert_eq!(board.is_in_check(PieceColor::BLACK), false);
+        assert_eq!(board.is_square_attacked(e4, PieceColor::WHITE), false);
+
+        board.set(Square::from_uci("e2").unwrap(), Some(Piece::new(PieceType::Queen, PieceColor::WHITE)));
+        assert_eq!(board.is_square_attacked(e4, PieceColor::WHITE), true);
+        assert_eq!(board.is_in_check(PieceColor::BLACK), true);
+    }
+
+    #[test]
+    fn explain_illegal() {
+        let board = Board::from_fen("4r1k1/8/8/8/8/2p5/4N3/R3K3 w - - 0 1").unwrap();
+        let explain = |m: &str| board.explain_illegal(Move::from_uci(m).unwrap());
+
+        assert_eq!(explain("a1a8"), None);
+        assert_eq!(explain("b2b3"), Some(IllegalReason::NoPieceAtStart));
+        assert_eq!(explain("c3c2"), Some(IllegalReason::WrongTurn));
+        assert_eq!(explain("e1e2"), Some(IllegalReason::CapturingOwnPiece));
+        assert_eq!(explain("a1b2"), Some(IllegalReason::PieceCannotMoveThatWay));
+        assert_eq!(explain("e1c1"), Some(IllegalReason::PieceCannotMoveThatWay));
+        assert_eq!(explain("a1f1"), Some(IllegalReason::PathBlocked));
+        assert_eq!(explain("e2c3"), Some(IllegalReason::LeavesKingInCheck));
+        assert_eq!(explain("a1a2q"), Some(IllegalReason::InvalidPromotion));
+    }
+
+    #[test]
+    fn in_check_after() {
+        // knight on e2 is pinned by rook on e8
+        let board = Board::from_fen("4r1k1/8/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.in_check_after(Move::from_uci("e2c3").unwrap()), true);
+        assert_eq!(board.in_check_after(Move::from_uci("e1d1").unwrap()), false);
+        assert_eq!(board.in_check_after(Move::from_uci("e1d2").unwrap()), false);
+        assert_eq!(board.in_check_after(Move::from_uci("a1a2").unwrap()), false);
+
+        // rook on e2 can still move along the pin line
+        let board = Board::from_fen("4r1k1/8/8/8/8/8/4R3/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.in_check_after(Move::from_uci("e2e5").unwrap()), false);
+        assert_eq!(board.in_check_after(Move::from_uci("e2d2").unwrap()), true);
+        assert_eq!(board.get_piece(Square::from_uci("e2").unwrap()).unwrap().piece_type(), PieceType::Rook);
+    }
+
+    #[test]
+    fn king_square() {
+        let mut board = Board::new();
+
+        assert_eq!(board.king_square(PieceColor::WHITE), Some(Square::from_uci("e1").unwrap()));
+        assert_eq!(board.king_square(PieceColor::BLACK), Some(Square::from_uci("e8").unwrap()));
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert!(board.apply_move(Move::from_uci("e7e5").unwrap()).is_ok());
+        assert!(board.apply_move(Move::from_uci("e1e2").unwrap()).is_ok());
+
+        assert_eq!(board.king_square(PieceColor::WHITE), Some(Square::from_uci("e2").unwrap()));
+
+        board.set(Square::from_uci("e8").unwrap(), None);
+        assert_eq!(board.king_square(PieceColor::BLACK), None);
+    }
+
+    #[test]
+    fn last_move() {
+        let mut board = Board::new();
+
+        assert_eq!(board.last_move(), None);
+
+        for m in ["e2e4", "e7e5", "g1f3"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+            assert_eq!(board.last_move(), Some(Move::from_uci(m).unwrap()));
+        }
+
+        board.undo();
+        assert_eq!(board.last_move(), Some(Move::from_uci("e7e5").unwrap()));
+
+        board.undo();
+        board.undo();
+        assert_eq!(board.last_move(), None);
+    }
+
+    #[test]
+    fn deep_apply_and_undo() {
+        let original = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let mut board = original.clone();
+        let mut played = 0;
+
+        // deterministic walk through varied moves, including castling, captures and promotions
+        for ply in 0..60 {
+            let moves = board.legal_moves(board.turn());
+
+            if moves.is_empty() {
+                break;
+            }
+
+            assert!(board.apply_move(moves[(ply * 7) % moves.len()]).is_ok());
+            played += 1;
+        }
+
+        assert!(played > 30);
+
+        for _ in 0..played {
+            assert!(board.undo().is_some());
+        }
+
+        assert!(board == original);
+        assert_eq!(board.last_move(), None);
+        assert_eq!(board.to_fen(), original.to_fen());
+        assert_eq!(board.zobrist_hash(), original.zobrist_hash());
+        assert_eq!(board.halfmove_clock(), original.halfmove_clock());
+        assert_eq!(board.en_passant(), original.en_passant());
+        assert_eq!(board.legal_moves(PieceColor::WHITE), original.legal_moves(PieceColor::WHITE));
+        assert_eq!(board.legal_moves(PieceColor::BLACK), original.legal_moves(PieceColor::BLACK));
+        assert_eq!(board.perft(2), original.perft(2));
+    }
+
+    #[test]
+    fn null_move() {
+        let mut board = Board::new();
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+
+        let before = board.clone();
+        board.make_null_move();
+
+        assert_eq!(board.turn(), PieceColor::WHITE);
+        assert_eq!(board.en_passant(), None);
+        assert_ne!(board.zobrist_hash(), before.zobrist_hash());
+
+        board.undo_null_move();
+
+        assert!(board == before);
+        assert_eq!(board.en_passant(), Some(Square::from_uci("e3").unwrap()));
+        assert_eq!(board.zobrist_hash(), before.zobrist_hash());
+        assert_eq!(board.to_fen(), before.to_fen());
+    }
+
+    #[test]
+    fn perft() {
+        let board = Board::new();
+
+        assert_eq!(board.perft(1), 20);
+        assert_eq!(board.perft(2), 400);
+        assert_eq!(board.perft(3), 8902);
+
+        // position with castling, en passant and pins to exercise move generation
+        let board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+
+        assert_eq!(board.perft(1), 48);
+        assert_eq!(board.perft(2), 2039);
+        assert_eq!(board.perft(3), 97862);
+
+        // every promotion choice is a separate move
+        let board = Board::from_fen("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8").unwrap();
+
+        assert_eq!(board.perft(1), 44);
+        assert_eq!(board.perft(2), 1486);
+        assert_eq!(board.perft(3), 62379);
+    }
+
+    #[test]
+    fn promotion_moves() {
+        let board = Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let a7 = Square::from_uci("a7").unwrap();
+        let moves: Vec<Move> = board.all_possible_moves_from_square(a7).collect();
+
+        assert_eq!(moves.len(), 4);
+
+        for promotion in [PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight] {
+            assert!(moves.contains(&Move::with_promotion(a7, Square::from_uci("a8").unwrap(), promotion)));
+        }
+
+        let board = Board::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+
+        assert_eq!(board.all_possible_moves_from_square(a7).count(), 8);
+        assert_eq!(board.san_to_move("axb8=N"), Ok(Move::from_uci("a7b8n").unwrap()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn render() {
+        let mut board = Board::new();
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+
+        assert_eq!(board.render(), "\
+8 ♜ ♞ ♝ ♛ ♚ ♝ ♞ ♜
+7 ♟ ♟ ♟ ♟ ♟ ♟ ♟ ♟
+6 . . . . . . . .
+5 . . . . . . . .
+4 . . . . ♙ . . .
+3 . . . . . . . .
+2 ♙ ♙ ♙ ♙ . ♙ ♙ ♙
+1 ♖ ♘ ♗ ♕ ♔ ♗ ♘ ♖
+  a b c d e f g h
+");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn render_ascii() {
+        assert_eq!(Board::new().render_ascii(), "\
+8 r n b q k b n r
+7 p p p p p p p p
+6 . . . . . . . .
+5 . . . . . . . .
+4 . . . . . . . .
+3 . . . . . . . .
+2 P P P P P P P P
+1 R N B Q K B N R
+  a b c d e f g h
+");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn render_from_black() {
+        let mut board = Board::new();
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert_eq!(board.render_from(PieceColor::WHITE), board.render());
+
+        let cells = |rendered: String| -> Vec<Vec<String>> {
+            rendered.lines().take(8).map(|line| line.split(' ').skip(1).map(String::from).collect()).collect()
+        };
+
+        let white = cells(board.render_from(PieceColor::WHITE));
+        let mut black = cells(board.render_from(PieceColor::BLACK));
+
+        // rotating black's view by 180 degrees gives white's view
+        black.reverse();
+        black.iter_mut().for_each(|row| row.reverse());
+
+        assert_eq!(black, white);
+
+        let rendered = board.render_from(PieceColor::BLACK);
+        let labels: Vec<&str> = rendered.lines().map(|line| line.split(' ').next().unwrap()).collect();
+
+        assert_eq!(labels, ["1", "2", "3", "4", "5", "6", "7", "8", ""]);
+        assert_eq!(rendered.lines().last(), Some("  h g f e d c b a"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn render_colored() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let rendered = board.render_colored();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "8 \x1b[43;37m   \x1b[42;37m   \x1b[43;37m   \x1b[42;37m   \x1b[43;30m ♚ \x1b[42;37m   \x1b[43;37m   \x1b[42;37m   \x1b[0m");
+        assert_eq!(lines[7], "1 \x1b[42;37m   \x1b[43;37m   \x1b[42;37m   \x1b[43;37m   \x1b[42;37m ♔ \x1b[43;37m   \x1b[42;37m   \x1b[43;37m   \x1b[0m");
+        assert_eq!(lines[8], "   a  b  c  d  e  f  g  h ");
+
+        // plain renderer has no escape codes
+        assert_eq!(board.render().contains('\x1b'), false);
+    }
+
+    #[test]
+    fn pieces_of_color() {
+        let mut board = Board::new();
+
+        assert_eq!(board.pieces(Some(PieceColor::WHITE)).count(), 16);
+        assert_eq!(board.pieces(Some(PieceColor::BLACK)).count(), 16);
+        assert_eq!(board.pieces(None).count(), 32);
+        assert!(board.pieces(Some(PieceColor::BLACK)).all(|(_, piece)| piece.color() == PieceColor::BLACK));
+
+        for m in ["e2e4", "d7d5", "e4d5"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.pieces(Some(PieceColor::WHITE)).count(), 16);
+        assert_eq!(board.pieces(Some(PieceColor::BLACK)).count(), 15);
+    }
+
+    #[test]
+    fn material_balance() {
+        let mut board = Board::new();
+
+        assert_eq!(board.material_balance(), 0);
+
+        board.set(Square::new(3, 0), None);
+        assert_eq!(board.material_balance(), -900);
+
+        let board = Board::from_fen("rnb1kbnr/pppppppp/8/8/8/8/PPPPPPP1/RNBQKBN1 w Qkq - 0 1").unwrap();
+        assert_eq!(board.material_balance(), 900 - 600);
+    }
+
+    #[test]
+    fn find_pieces() {
+        let mut board = Board::new();
+
+        assert_eq!(board.find_pieces(PieceType::Rook, PieceColor::WHITE), [Square::from_uci("a1").unwrap(), Square::from_uci("h1").unwrap()]);
+        assert_eq!(board.find_pieces(PieceType::Pawn, PieceColor::BLACK).len(), 8);
+
+        for m in ["e2e4", "d7d5", "e4d5", "d8d5", "b1c3"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.find_pieces(PieceType::Queen, PieceColor::BLACK), [Square::from_uci("d5").unwrap()]);
+        assert_eq!(board.find_pieces(PieceType::Knight, PieceColor::WHITE), [Square::from_uci("g1").unwrap(), Square::from_uci("c3").unwrap()]);
+        assert!(Board::new_clear().find_pieces(PieceType::King, PieceColor::WHITE).is_empty());
+    }
+
+    #[test]
+    fn piece_count() {
+        let mut board = Board::new();
+
+        assert_eq!(board.piece_count(), 32);
+        assert_eq!(Board::new_clear().piece_count(), 0);
+
+        for m in ["e2e4", "d7d5", "e4d5"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.piece_count(), 31);
+
+        board.undo();
+        assert_eq!(board.piece_count(), 32);
+    }
+
+    #[test]
+    fn material_count() {
+        let mut board = Board::new();
+
+        for color in PieceColor::ALL {
+            assert_eq!(board.material_count(color), [8, 2, 2, 2, 1, 1]);
+        }
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert!(board.apply_move(Move::from_uci("d7d5").unwrap()).is_ok());
+        assert!(board.apply_move(Move::from_uci("e4d5").unwrap()).is_ok());
+        assert!(board.apply_move(Move::from_uci("d8d5").unwrap()).is_ok());
+
+        assert_eq!(board.material_count(PieceColor::WHITE), [7, 2, 2, 2, 1, 1]);
+        assert_eq!(board.material_count(PieceColor::BLACK), [7, 2, 2, 2, 1, 1]);
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K2R w - - 0 1").unwrap();
+        assert_eq!(board.material_count(PieceColor::WHITE)[PieceType::Rook as usize], 2);
+        assert_eq!(board.material_count(PieceColor::BLACK), [0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn evaluate_with() {
+        // starting position with black's a-pawn missing
+        let board = Board::from_fen("rnbqkbnr/1ppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let mut params = EvalParams::default();
+
+        assert_eq!(board.evaluate_with(&params), 100);
+        assert_eq!(board.evaluate_with(&params), board.material_balance());
+
+        params.piece_values[PieceType::Pawn as usize] *= 2;
+        assert_eq!(board.evaluate_with(&params), 200);
+
+        // both sides have bishop pair, white lost a-pawn and d-pawn and has doubled c-pawns
+        let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/2P5/1PP1PPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+        let params = EvalParams { bishop_pair: 30, doubled_pawn: -15, isolated_pawn: -10, ..EvalParams::default() };
+
+        assert_eq!(board.evaluate_with(&params), -100 - 15);
+
+        // a-pawn and h-pawn are both isolated
+        let board = Board::from_fen("4k3/8/8/8/8/8/P6P/4K3 w - - 0 1").unwrap();
+        assert_eq!(board.evaluate_with(&params), 200 - 2 * 10);
+    }
+
+    #[test]
+    fn order_moves() {
+        // queen on d5 can be taken by rook and bishop, knight on f6 by pawn, pawn on b5 by bishop
+        let board = Board::from_fen("4k3/8/5n2/1p1qP3/2B5/8/8/3R2K1 w - - 0 1").unwrap();
+        let mut moves = board.legal_moves(board.turn());
+
+        board.order_moves(&mut moves);
+
+        let first: Vec<String> = moves.iter().take(4).map(|m| m.to_uci()).collect();
+        assert_eq!(first, ["c4d5", "d1d5", "e5f6", "c4b5"]);
+
+        assert!(moves[4..].iter().all(|m| board.get_piece(m.end()).is_none()));
+    }
+
+    #[test]
+    fn best_move() {
+        // hanging queen
+        let board = Board::from_fen("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1").unwrap();
+        assert_eq!(board.best_move(1), Some(Move::from_uci("d1d5").unwrap()));
+        assert_eq!(board.best_move(2), Some(Move::from_uci("d1d5").unwrap()));
+
+        // mate in one is preferred over winning material
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/q7/3RK3 w - - 0 1").unwrap();
+        assert_eq!(board.best_move(2), Some(Move::from_uci("d1d8").unwrap()));
+
+        // checkmate and stalemate
+        assert_eq!(Board::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").unwrap().best_move(2), None);
+        assert_eq!(Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap().best_move(2), None);
+    }
+
+    #[test]
+    fn new_standard() {
+        let board = Board::new_standard();
+
+        assert_eq!(board.to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        assert!(board == Board::new());
+        assert!(board.pieces(None).all(|(_, piece)| !piece.has_moved()));
+    }
+
+    #[test]
+    fn chess960_positions() {
+        let back_rank = |board: &Board| -> String {
+            (0..8).map(|x| board.piece_at(x, 0).unwrap().get_character()).collect()
+        };
+
+        assert_eq!(back_rank(&Board::new_chess960(0).unwrap()), "BBQNNRKR");
+        assert_eq!(back_rank(&Board::new_chess960(518).unwrap()), "RNBQKBNR");
+        assert_eq!(back_rank(&Board::new_chess960(959).unwrap()), "RKRNNQBB");
+
+        assert!(Board::new_chess960(960).is_none());
+        assert!(Board::new_chess960(u16::MAX).is_none());
+
+        for id in 0..960 {
+            let board = Board::new_chess960(id).unwrap();
+            let rank = back_rank(&board);
+
+            let bishops: Vec<usize> = rank.match_indices('B').map(|(i, _)| i).collect();
+            let (king, rooks) = (rank.find('K').unwrap(), (rank.find('R').unwrap(), rank.rfind('R').unwrap()));
+
+            assert_eq!(bishops[0] % 2 != bishops[1] % 2, true);
+            assert_eq!(rooks.0 < king && king < rooks.1, true);
+            assert_eq!(board.to_fen().split(' ').next().unwrap().split('/').next().unwrap(), rank.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn chess960_castling() {
+        // king on f1 and rook on g1 swap places
+        let mut board = Board::new_chess960(3).unwrap();
+
+        assert!(board.apply_move(Move::from_uci("f1g1").unwrap()).is_ok());
+        assert_eq!(board.to_fen(), "bqnnrkrb/pppppppp/8/8/8/8/PPPPPPPP/BQNNRRKB b kq - 1 1");
+
+        board.undo();
+        assert_eq!(board.to_fen(), Board::new_chess960(3).unwrap().to_fen());
+
+        // standard arrangement, king moves onto rook instead of two squares
+        let mut board = Board::new_chess960(518).unwrap();
+
+        for m in ["e2e4", "e7e5", "g1f3", "g8f6", "f1c4", "f8c5"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.check_move_possibility(Move::from_uci("e1g1").unwrap()).is_ok(), false);
+        assert_eq!(board.move_to_san(Move::from_uci("e1h1").unwrap()), "O-O");
+        assert_eq!(board.san_to_move("O-O"), Ok(Move::from_uci("e1h1").unwrap()));
+
+        assert!(board.apply_move(Move::from_uci("e1h1").unwrap()).is_ok());
+        assert_eq!(board.to_fen(), "rnbqk2r/pppp1ppp/5n2/2b1p3/2B1P3/5N2/PPPP1PPP/RNBQ1RK1 b kq - 5 4");
+
+        // every square king and rook pass through has to be empty
+        let mut board = Board::new_chess960(959).unwrap();
+
+        assert_eq!(board.check_move_possibility(Move::from_uci("b1c1").unwrap()).is_ok(), false);
+
+        for m in ["d1c3", "d8c6", "e1d3", "e8d6", "e2e4", "e7e5", "f1e2", "f8e7", "f2f3", "f7f6"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.check_move_possibility(Move::from_uci("b1c1").unwrap()).is_ok(), false);
+
+        assert!(board.apply_move(Move::from_uci("g1f2").unwrap()).is_ok());
+        assert!(board.apply_move(Move::from_uci("g8f7").unwrap()).is_ok());
+
+        assert_eq!(board.move_to_san(Move::from_uci("b1c1").unwrap()), "O-O");
+        assert!(board.apply_move(Move::from_uci("b1c1").unwrap()).is_ok());
+        assert_eq!(board.to_fen(), "rkr4b/ppppqbpp/2nn1p2/4p3/4P3/2NN1P2/PPPPQBPP/R4RKB b kq - 3 7");
+    }
+
+    #[test]
+    fn chess960_fen_castling() {
+        // Shredder-FEN names castling rooks by their files
+        let board = Board::from_fen("bqnnrkrb/pppppppp/8/8/8/8/PPPPPPPP/BQNNRKRB w GEge - 0 1").unwrap();
+
+        assert_eq!(board.is_chess960(), true);
+        assert!(board == Board::new_chess960(3).unwrap());
+        assert_eq!(board.to_fen(), "bqnnrkrb/pppppppp/8/8/8/8/PPPPPPPP/BQNNRKRB w KQkq - 0 1");
+        assert_eq!(Board::from_fen(&board.to_fen()).unwrap().to_fen(), board.to_fen());
+
+        // X-FEN uses file only when castling rook is not the outermost one
+        let fen = "1k6/8/8/8/8/8/8/1K1R3R w D - 0 1";
+        let board = Board::from_fen(fen).unwrap();
+
+        assert_eq!(board.is_chess960(), true);
+        assert_eq!(board.to_fen(), fen);
+        assert_eq!(board.san_to_move("O-O"), Ok(Move::from_uci("b1d1").unwrap()));
+
+        // standard castling stays standard
+        assert_eq!(Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w HAha - 0 1").unwrap().is_chess960(), false);
+        assert_eq!(Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w HAha - 0 1").unwrap().to_fen(), "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+
+        assert_eq!(Board::from_fen("1k6/8/8/8/8/8/8/1K1R3R w C - 0 1").err(), Some(FenError::InvalidCastling('C')));
+        assert_eq!(Board::from_fen("1k6/8/8/8/8/8/8/1K1R3R w x - 0 1").err(), Some(FenError::InvalidCastling('x')));
+    }
+
+    #[test]
+    fn game_state() {
+        assert_eq!(Board::new().state(), GameState::Ongoing);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap().state(), GameState::Check(PieceColor::WHITE));
+        assert_eq!(Board::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1").unwrap().state(), GameState::Checkmate(PieceColor::WHITE));
+        assert_eq!(Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap().state(), GameState::Stalemate);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap().state(), GameState::DrawByInsufficientMaterial);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 100 1").unwrap().state(), GameState::DrawByFiftyMove);
+
+        // checkmate on the hundredth halfmove still wins
+        assert_eq!(Board::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 100 1").unwrap().state(), GameState::Checkmate(PieceColor::WHITE));
+
+        let mut board = Board::new();
+
+        for m in ["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1", "f6g8"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.state(), GameState::DrawByRepetition);
+    }
+
+    #[test]
+    fn outcome() {
+        assert_eq!(Board::new().outcome(), None);
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap().outcome(), None);
+        assert_eq!(Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap().outcome(), Some(Outcome::Stalemate));
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4KN2 w - - 0 1").unwrap().outcome(), Some(Outcome::DrawByInsufficientMaterial));
+        assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 100 1").unwrap().outcome(), Some(Outcome::DrawByFiftyMove));
+
+        // checkmate past the fifty move limit is still checkmate, not a draw
+        assert_eq!(Board::from_fen("7k/6Q1/6K1/8/8/8/8/8 b - - 120 1").unwrap().outcome(), Some(Outcome::Checkmate(PieceColor::WHITE)));
+
+        let mut board = Board::new();
+
+        for m in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(board.outcome(), Some(Outcome::Checkmate(PieceColor::BLACK)));
+    }
+
+    #[test]
+    fn repetition_key() {
+        let board = Board::from_fen("4k3/8/8/8/3p4/8/4P3/4K3 w - - 0 1").unwrap();
+        let later = Board::from_fen("4k3/8/8/8/3p4/8/4P3/4K3 w - - 37 60").unwrap();
+
+        assert_eq!(board.repetition_key(), later.repetition_key());
+
+        // en passant capture is possible, so position differs from one without en passant square
+        let mut board = board;
+        let without = Board::from_fen("4k3/8/8/8/3pP3/8/8/4K3 b - - 0 1").unwrap();
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert_ne!(board.repetition_key(), without.repetition_key());
+
+        // pawn can't capture, position is the same
+        let mut board = Board::from_fen("4k3/8/8/8/2p5/8/4P3/4K3 w - - 0 1").unwrap();
+        let without = Board::from_fen("4k3/8/8/8/2p1P3/8/8/4K3 b - - 0 1").unwrap();
+
+        assert!(board.apply_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert_eq!(board.repetition_key(), without.repetition_key());
+    }
+
+    #[test]
+    fn pinned_pieces() {
+        // Bb5 pins knight on c6 to the king
+        let mut board = Board::new();
+
+        for m in ["e2e4", "d7d6", "f1b5", "b8c6"] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        let pins = board.pinned_pieces(PieceColor::BLACK);
+
+        assert_eq!(pins, vec![(Square::from_uci("c6").unwrap(), Square::from_uci("b5").unwrap())]);
+        assert!(board.pinned_pieces(PieceColor::WHITE).is_empty());
+
+        // two pieces between attacker and king is not a pin
+        let board = Board::from_fen("4k3/4p3/4n3/8/8/8/8/4R1K1 w - - 0 1").unwrap();
+        assert!(board.pinned_pieces(PieceColor::BLACK).is_empty());
+
+        // pinned piece can still move along the pin line
+        let board = Board::from_fen("4k3/4r3/8/8/8/8/8/4R1K1 b - - 0 1").unwrap();
+
+        assert_eq!(board.pinned_pieces(PieceColor::BLACK), vec![(Square::from_uci("e7").unwrap(), Square::from_uci("e1").unwrap())]);
+        assert_eq!(board.check_move_possibility(Move::from_uci("e7e1").unwrap()).is_ok(), true);
+        assert_eq!(board.check_move_possibility(Move::from_uci("e7a7").unwrap()).is_ok(), false);
+    }
+
+    #[test]
+    fn attackers_of() {
+        let board = Board::from_fen("4k3/8/8/3r4/8/2n5/8/3R2K1 w - - 0 1").unwrap();
+        let d1 = Square::from_uci("d1").unwrap();
+
+        let mut attackers = board.attackers_of(d1, PieceColor::BLACK);
+        attackers.sort_by_key(|square| square.to_index());
+
+        assert_eq!(attackers, vec![Square::from_uci("c3").unwrap(), Square::from_uci("d5").unwrap()]);
+        assert!(board.attackers_of(d1, PieceColor::WHITE).is_empty());
+
+        // blocked slider doesn't attack
+        let board = Board::from_fen("4k3/8/8/3r4/3P4/8/8/3R2K1 w - - 0 1").unwrap();
+        assert!(board.attackers_of(d1, PieceColor::BLACK).is_empty());
+    }
+
+    #[test]
+    fn null_move_rejected() {
+        let types = [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen, PieceType::King];
+        let e2 = Square::from_uci("e2").unwrap();
+
+        for piece_type in types {
+            let mut board = Board::from_fen("k7/8/8/8/8/8/8/7K w - - 0 1").unwrap();
+            board.set(e2, Some(Piece::new(piece_type, PieceColor::WHITE)));
+
+            assert_eq!(board.check_move_possibility(Move::new(e2, e2)), Err(MoveFailReason::IllegalMove));
+            assert_eq!(board.apply_move(Move::new(e2, e2)).err(), Some(MoveFailReason::IllegalMove));
+        }
+
+        assert_eq!(Board::new_clear().check_move_possibility(Move::new(e2, e2)), Err(MoveFailReason::IllegalMove));
+    }
+
+    #[test]
+    fn path_clear() {
+        let board = Board::from_fen("4k3/8/8/3p4/8/1B6/8/R2n2K1 w - - 0 1").unwrap();
+
+        // rook blocked by knight on d1
+        assert_eq!(board.is_path_clear(Move::from_uci("a1c1").unwrap()), true);
+        assert_eq!(board.is_path_clear(Move::from_uci("a1d1").unwrap()), true);
+        assert_eq!(board.is_path_clear(Move::from_uci("a1f1").unwrap()), false);
+        assert_eq!(board.check_move_possibility(Move::from_uci("a1d1").unwrap()).is_ok(), true);
+        assert_eq!(board.check_move_possibility(Move::from_uci("a1f1").unwrap()).is_ok(), false);
+
+        // bishop blocked by pawn on d5
+        assert_eq!(board.is_path_clear(Move::from_uci("b3d5").unwrap()), true);
+        assert_eq!(board.is_path_clear(Move::from_uci("b3f7").unwrap()), false);
+        assert_eq!(board.check_move_possibility(Move::from_uci("b3d5").unwrap()).is_ok(), true);
+        assert_eq!(board.check_move_possibility(Move::from_uci("b3e6").unwrap()).is_ok(), false);
+    }
@@ -0,0 +1,50 @@
+use chess_api::board::*;
+use chess_api::book::{BookError, OpeningBook};
+use chess_api::movement::*;
+
+const BOOK: &str = "
+# open games
+rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 -> e2e4
+rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 -> d2d4
+rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1 -> e7e5
+rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2 -> g1f3
+";
+
+#[test]
+    fn probe() {
+        let book: OpeningBook = BOOK.parse().unwrap();
+        let mut board = Board::new();
+
+        assert_eq!(book.len(), 3);
+        assert_eq!(book.moves(&board), [Move::from_uci("e2e4").unwrap(), Move::from_uci("d2d4").unwrap()]);
+
+        for (m, expected) in [("e2e4", "e7e5"), ("e7e5", "g1f3")] {
+            assert!(board.apply_move(Move::from_uci(m).unwrap()).is_ok());
+            assert_eq!(book.probe(&board), Some(Move::from_uci(expected).unwrap()));
+        }
+
+        assert!(board.apply_move(Move::from_uci("g1f3").unwrap()).is_ok());
+        assert_eq!(book.probe(&board), None);
+        assert!(book.moves(&board).is_empty());
+    }
+
+    #[test]
+    fn add() {
+        let mut book = OpeningBook::new();
+        let board = Board::new();
+
+        assert!(book.is_empty());
+
+        book.add(&board, Move::from_uci("c2c4").unwrap());
+        book.add(&board, Move::from_uci("c2c4").unwrap());
+
+        assert_eq!(book.moves(&board), [Move::from_uci("c2c4").unwrap()]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("4k3/8/8/8/8/8/8/4K3 w - - 0 1 e1e2".parse::<OpeningBook>().err(), Some(BookError::InvalidLine("4k3/8/8/8/8/8/8/4K3 w - - 0 1 e1e2".to_string())));
+        assert_eq!("4k3/8/8/8/8/8/4K3 w - - 0 1 -> e1e2".parse::<OpeningBook>().err(), Some(BookError::InvalidFen(FenError::WrongRankCount(7))));
+        assert_eq!("4k3/8/8/8/8/8/8/4K3 w - - 0 1 -> e1e3".parse::<OpeningBook>().err(), Some(BookError::InvalidMove("e1e3".to_string())));
+        assert_eq!("4k3/8/8/8/8/8/8/4K3 w - - 0 1 -> e8e7".parse::<OpeningBook>().err(), Some(BookError::InvalidMove("e8e7".to_string())));
+    }
@@ -0,0 +1,31 @@
+use chess_api::board::*;
+use chess_api::epd::{self, EpdError};
+use chess_api::movement::*;
+
+#[test]
+    fn parse() {
+        let epd = epd::parse("4k3/8/4p3/8/8/8/8/4QK2 w - - bm Qxe6+; id \"WAC.001\";").unwrap();
+
+        assert_eq!(epd.best_moves(), [Move::from_uci("e1e6").unwrap()]);
+        assert_eq!(epd.operation("bm"), Some("Qxe6+"));
+        assert_eq!(epd.operation("id"), Some("WAC.001"));
+        assert_eq!(epd.operations().len(), 2);
+        assert_eq!(epd.board().to_fen(), Board::from_fen("4k3/8/4p3/8/8/8/8/4QK2 w - - 0 1").unwrap().to_fen());
+    }
+
+    #[test]
+    fn parse_quoted_semicolon() {
+        let epd = epd::parse("4k3/8/8/8/8/8/8/4K3 b - - c0 \"draw; bare kings\"; id \"kings\"").unwrap();
+
+        assert!(epd.best_moves().is_empty());
+        assert_eq!(epd.operation("c0"), Some("draw; bare kings"));
+        assert_eq!(epd.operation("id"), Some("kings"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(epd::parse("4k3/8/8/8/8/8/8/4K3 w -").err(), Some(EpdError::WrongFieldCount(3)));
+        assert_eq!(epd::parse("4k3/8/8/8/8/8/4K3 w - -").err(), Some(EpdError::InvalidFen(FenError::WrongRankCount(7))));
+        assert_eq!(epd::parse("4k3/8/8/8/8/8/8/4K3 w - - id \"open;").err(), Some(EpdError::UnterminatedString));
+        assert_eq!(epd::parse("4k3/8/8/8/8/8/8/4K3 w - - bm Qh5;").err(), Some(EpdError::InvalidBestMove("Qh5".to_string(), SanError::IllegalMove)));
+    }
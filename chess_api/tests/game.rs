@@ -0,0 +1,289 @@
+use chess_api::board::MoveFailReason;
+use chess_api::game::*;
+use chess_api::movement::*;
+use chess_api::piece::{PieceColor, PieceType};
+
+#[test]
+    fn to_pgn() {
+        let mut game = Game::new();
+
+        game.set_tag("White", "Alice");
+        game.set_tag("Black", "Bob");
+
+        for m in ["e2e4", "e7e5", "f1c4", "b8c6", "d1h5", "g8f6", "h5f7"] {
+            assert!(game.make_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(game.result(), GameResult::WhiteWins);
+        assert_eq!(game.to_pgn(), "\
+[Event \"?\"]
+[Site \"?\"]
+[Date \"????.??.??\"]
+[Round \"?\"]
+[White \"Alice\"]
+[Black \"Bob\"]
+[Result \"1-0\"]
+
+1. e4 e5 2. Bc4 Nc6 3. Qh5 Nf6 4. Qxf7# 1-0
+");
+    }
+
+    #[test]
+    fn to_pgn_wraps_lines() {
+        let mut game = Game::new();
+
+        for m in [
+            "a2a3", "a7a6", "b2b3", "b7b6", "c2c3", "c7c6", "d2d3", "d7d6", "e2e3", "e7e6", "f2f3", "f7f6",
+            "g2g3", "g7g6", "h2h3", "h7h6", "a3a4", "a6a5", "b3b4", "b6b5", "c3c4", "c6c5", "d3d4", "d6d5"
+        ] {
+            assert!(game.make_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        game.set_result(GameResult::Draw);
+
+        let pgn = game.to_pgn();
+        let movetext: Vec<&str> = pgn.split("\n\n").nth(1).unwrap().lines().collect();
+
+        assert!(movetext.len() > 1);
+        assert!(movetext.iter().all(|line| line.len() <= 80));
+        assert!(movetext.last().unwrap().ends_with("1/2-1/2"));
+        assert_eq!(game.tag("Result"), Some("1/2-1/2"));
+    }
+
+    #[test]
+    fn from_pgn() {
+        let pgn = "\
+[Event \"Casual game\"]
+[Site \"?\"]
+[Date \"2024.01.01\"]
+[Round \"?\"]
+[White \"Alice\"]
+[Black \"Bob \\\"the rook\\\"\"]
+[Result \"1/2-1/2\"]
+
+1. e4 e5 2. Nf3 {main line} Nc6 3. Bb5 a6 (3... Nf6 4. 0-0) 4. Ba4 Nf6 5.O-O Be7 $6
+6. Re1 b5 7. Bb3 d6 ; closed
+8. c3 1/2-1/2 9. h3";
+
+        let game = Game::from_pgn(pgn).unwrap();
+
+        assert_eq!(game.tag("Event"), Some("Casual game"));
+        assert_eq!(game.tag("Black"), Some("Bob \"the rook\""));
+        assert_eq!(game.result(), GameResult::Draw);
+        assert_eq!(game.moves().len(), 15);
+        assert_eq!(game.board().to_fen(), "r1bqk2r/2p1bppp/p1np1n2/1p2p3/4P3/1BP2N2/PP1P1PPP/RNBQR1K1 b kq - 0 8");
+
+        // exported game loads back to the same position
+        let loaded = Game::from_pgn(&game.to_pgn()).unwrap();
+
+        assert_eq!(loaded.to_pgn(), game.to_pgn());
+        assert!(loaded.board() == game.board());
+    }
+
+    #[test]
+    fn from_pgn_errors() {
+        assert_eq!(Game::from_pgn("[Event ?]\n\n1. e4 *").err(), Some(PgnError::InvalidTag(String::from("[Event ?]"))));
+        assert_eq!(Game::from_pgn("1. e4 {unfinished").err(), Some(PgnError::UnterminatedComment));
+        assert!(matches!(Game::from_pgn("1. e4 e4 *"), Err(PgnError::InvalidMove(_, _))));
+    }
+
+    #[test]
+    fn resign() {
+        let mut game = Game::new();
+
+        assert!(game.make_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        game.resign(PieceColor::WHITE);
+
+        assert_eq!(game.result(), GameResult::BlackWins);
+        assert_eq!(game.tag("Result"), Some("0-1"));
+
+        // finished game can not be resigned again
+        game.resign(PieceColor::BLACK);
+        assert_eq!(game.result(), GameResult::BlackWins);
+    }
+
+    #[test]
+    fn no_moves_after_game_over() {
+        let mut game = Game::new();
+
+        assert_eq!(game.make_move(Move::from_uci("e2e5").unwrap()).err(), Some(GameMoveError::Illegal(MoveFailReason::IllegalMove)));
+
+        game.resign(PieceColor::WHITE);
+        assert_eq!(game.make_move(Move::from_uci("e2e4").unwrap()).err(), Some(GameMoveError::GameOver));
+
+        let mut game = Game::new();
+
+        game.offer_draw(PieceColor::WHITE);
+        assert!(game.accept_draw(PieceColor::BLACK));
+        assert_eq!(game.make_move(Move::from_uci("e2e4").unwrap()).err(), Some(GameMoveError::GameOver));
+        assert!(game.moves().is_empty());
+
+        let mut game = Game::new();
+
+        for m in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+            assert!(game.make_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(game.make_move(Move::from_uci("e1f2").unwrap()).err(), Some(GameMoveError::GameOver));
+        assert_eq!(game.moves().len(), 4);
+    }
+
+    #[test]
+    fn draw_by_repetition() {
+        let mut game = Game::new();
+        let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+
+        for m in shuffle.iter().chain(shuffle.iter()) {
+            assert_eq!(game.result(), GameResult::Ongoing);
+            assert!(game.make_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(game.result(), GameResult::Draw);
+        assert_eq!(game.tag("Result"), Some("1/2-1/2"));
+        assert!(game.to_pgn().ends_with("4. Ng1 Ng8 1/2-1/2\n"));
+        assert_eq!(game.make_move(Move::from_uci("e2e4").unwrap()).err(), Some(GameMoveError::GameOver));
+
+        // unclaimed repetition doesn't stop loading of recorded game
+        let game = Game::from_pgn("1. Nf3 Nf6 2. Ng1 Ng8 3. Nf3 Nf6 4. Ng1 Ng8 5. e4 *").unwrap();
+
+        assert_eq!(game.moves().len(), 9);
+        assert_eq!(game.result(), GameResult::Ongoing);
+    }
+
+    #[test]
+    fn undo_keeps_result_not_following_from_position() {
+        let mut game = Game::new();
+
+        assert!(game.make_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        game.resign(PieceColor::BLACK);
+
+        assert_eq!(game.undo(), None);
+        assert_eq!(game.result(), GameResult::WhiteWins);
+        assert_eq!(game.moves().len(), 1);
+
+        let mut game = Game::new();
+
+        assert!(game.make_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        game.resign(PieceColor::WHITE);
+
+        assert_eq!(game.undo(), None);
+        assert_eq!(game.result(), GameResult::BlackWins);
+
+        let mut game = Game::new();
+
+        assert!(game.make_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        game.offer_draw(PieceColor::WHITE);
+        assert!(game.accept_draw(PieceColor::BLACK));
+
+        assert_eq!(game.undo(), None);
+        assert_eq!(game.result(), GameResult::Draw);
+    }
+
+    #[test]
+    fn draw_offer() {
+        let mut game = Game::new();
+
+        game.offer_draw(PieceColor::WHITE);
+        assert_eq!(game.draw_offer(), Some(PieceColor::WHITE));
+
+        // black declines by moving
+        assert!(game.make_move(Move::from_uci("e2e4").unwrap()).is_ok());
+        assert!(game.make_move(Move::from_uci("e7e5").unwrap()).is_ok());
+        assert_eq!(game.draw_offer(), None);
+        assert!(!game.accept_draw(PieceColor::BLACK));
+
+        game.offer_draw(PieceColor::BLACK);
+        assert!(game.accept_draw(PieceColor::WHITE));
+        assert_eq!(game.result(), GameResult::Draw);
+    }
+
+    #[test]
+    fn captured_by() {
+        let mut game = Game::new();
+
+        for m in ["e2e4", "d7d5", "e4d5", "d8d5", "b1c3", "d5a2", "a1a2"] {
+            assert!(game.make_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(game.captured_by(PieceColor::WHITE), [PieceType::Pawn, PieceType::Queen]);
+        assert_eq!(game.captured_by(PieceColor::BLACK), [PieceType::Pawn, PieceType::Pawn]);
+
+        assert_eq!(game.undo(), Some(Move::from_uci("a1a2").unwrap()));
+        assert_eq!(game.captured_by(PieceColor::WHITE), [PieceType::Pawn]);
+
+        assert_eq!(game.undo(), Some(Move::from_uci("d5a2").unwrap()));
+        assert_eq!(game.captured_by(PieceColor::BLACK), [PieceType::Pawn]);
+        assert_eq!(game.moves().len(), 5);
+    }
+
+    #[test]
+    fn undo() {
+        let mut game = Game::new();
+
+        assert_eq!(game.undo(), None);
+
+        for m in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+            assert!(game.make_move(Move::from_uci(m).unwrap()).is_ok());
+        }
+
+        assert_eq!(game.result(), GameResult::BlackWins);
+        assert_eq!(game.undo(), Some(Move::from_uci("d8h4").unwrap()));
+        assert_eq!(game.result(), GameResult::Ongoing);
+        assert_eq!(game.tag("Result"), Some("*"));
+        assert!(game.to_pgn().ends_with("\n\n1. f3 e5 2. g4 *\n"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn clock_flags_player_out_of_time() {
+        use std::time::{Duration, Instant};
+
+        let mut clock = Clock::new(Duration::from_secs(60), Duration::from_secs(1));
+        let start = Instant::now();
+
+        clock.start_turn_at(PieceColor::WHITE, start);
+        clock.start_turn_at(PieceColor::BLACK, start + Duration::from_secs(20));
+
+        assert_eq!(clock.remaining_at(PieceColor::WHITE, start + Duration::from_secs(90)), Duration::from_secs(41));
+        assert!(!clock.flagged_at(PieceColor::BLACK, start + Duration::from_secs(79)));
+        assert!(clock.flagged_at(PieceColor::BLACK, start + Duration::from_secs(81)));
+
+        // increment doesn't save player who already ran out of time
+        clock.end_turn_at(start + Duration::from_secs(81));
+        assert_eq!(clock.remaining_at(PieceColor::BLACK, start + Duration::from_secs(81)), Duration::ZERO);
+        assert!(clock.flagged_at(PieceColor::BLACK, start + Duration::from_secs(81)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn game_lost_on_time() {
+        use std::time::Duration;
+
+        let mut game = Game::new();
+
+        game.set_clock(Clock::new(Duration::ZERO, Duration::ZERO));
+
+        assert!(game.check_flag());
+        assert_eq!(game.result(), GameResult::BlackWins);
+        assert_eq!(game.tag("Result"), Some("0-1"));
+        assert_eq!(game.make_move(Move::from_uci("e2e4").unwrap()).err(), Some(GameMoveError::GameOver));
+
+        let mut game = Game::new();
+
+        game.set_clock(Clock::new(Duration::from_secs(60), Duration::ZERO));
+        assert!(game.make_move(Move::from_uci("e2e4").unwrap()).is_ok());
+
+        assert!(!game.check_flag());
+        assert_eq!(game.result(), GameResult::Ongoing);
+        assert_eq!(game.clock().unwrap().running(), Some(PieceColor::BLACK));
+
+        // move made after flag fell is not recorded
+        game.set_clock(Clock::new(Duration::ZERO, Duration::ZERO));
+        assert_eq!(game.make_move(Move::from_uci("e7e5").unwrap()).err(), Some(GameMoveError::GameOver));
+
+        assert_eq!(game.result(), GameResult::WhiteWins);
+        assert_eq!(game.moves(), [Move::from_uci("e2e4").unwrap()]);
+        assert_eq!(game.undo(), None);
+        assert!(game.to_pgn().ends_with("\n\n1. e4 1-0\n"));
+        assert_eq!(game.clock().unwrap().running(), None);
+    }
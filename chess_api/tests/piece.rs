@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use chess_api::board::*;
 use chess_api::piece::*;
 use chess_api::movement::*;
@@ -310,6 +312,52 @@ use chess_api::movement::*;
         assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 1), Square::new(3, 2))).is_ok(), true);
     }
 
+    #[test]
+    fn knight_and_king_moves_from_every_square() {
+        for piece_type in [PieceType::Knight, PieceType::King] {
+            let piece = Piece::new(piece_type, PieceColor::WHITE);
+
+            for start in 0..64 {
+                for end in 0..64 {
+                    let m = Move::new(Square::from_index(start).unwrap(), Square::from_index(end).unwrap());
+                    let (dx, dy) = m.to_deltas();
+
+                    let expected = match piece_type {
+                        PieceType::Knight => (dx == 2 && dy == 1) || (dx == 1 && dy == 2),
+                        _ => dx <= 1 && dy <= 1 && start != end
+                    };
+
+                    assert_eq!(piece.move_kind(m, false) == MoveKind::Normal, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn knight_and_king_in_corners() {
+        for (piece_type, expected) in [(PieceType::Knight, 2), (PieceType::King, 3)] {
+            for corner in [Square::new(0, 0), Square::new(7, 0), Square::new(0, 7), Square::new(7, 7)] {
+                let mut board = Board::new_clear();
+
+                board.set(corner, Some(Piece::new(piece_type, PieceColor::WHITE)));
+
+                let attacked = (0..64)
+                    .map(|i| Square::from_index(i).unwrap())
+                    .filter(|square| board.is_square_attacked(*square, PieceColor::WHITE))
+                    .collect::<Vec<Square>>();
+
+                assert_eq!(board.all_possible_moves_from_square(corner).count(), expected);
+                assert_eq!(attacked.len(), expected);
+
+                // nothing wraps around to the opposite edge
+                for square in attacked {
+                    let (dx, dy) = Move::new(corner, square).to_deltas();
+                    assert_eq!(dx <= 2 && dy <= 2, true);
+                }
+            }
+        }
+    }
+
     #[test]
     fn queen_rook_basic_movement() {
         let mut board = Board::new_clear();
@@ -522,3 +570,79 @@ use chess_api::movement::*;
         assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 1), Square::new(1, 6))).is_ok(), false);
         assert_eq!(board.check_move_possibility(Move::new(Square::new(1, 1), Square::new(6, 1))).is_ok(), false);
     }
+
+    #[test]
+    fn piece_characters() {
+        let types = [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen, PieceType::King];
+
+        let white: String = types.iter().map(|t| Piece::new(*t, PieceColor::WHITE).get_character()).collect();
+        let black: String = types.iter().map(|t| Piece::new(*t, PieceColor::BLACK).get_character()).collect();
+
+        assert_eq!(white, "PNBRQK");
+        assert_eq!(black, "pnbrqk");
+    }
+
+    #[test]
+    fn piece_from_char() {
+        for c in "PNBRQKpnbrqk".chars() {
+            let piece = Piece::from_char(c).unwrap();
+
+            assert_eq!(piece.get_character(), c);
+            assert_eq!(piece.has_moved(), false);
+        }
+
+        for c in "xX1 -".chars() {
+            assert!(Piece::from_char(c).is_none());
+        }
+    }
+
+    #[test]
+    fn null_move_impossible() {
+        let types = [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen, PieceType::King];
+        let square = Square::new(3, 3);
+
+        for piece_type in types {
+            for color in [PieceColor::WHITE, PieceColor::BLACK] {
+                let piece = Piece::new(piece_type, color);
+
+                assert_eq!(piece.can_move_to(Move::new(square, square), false).0, false);
+                assert_eq!(piece.can_move_to(Move::new(square, square), true).0, false);
+            }
+        }
+    }
+
+    #[test]
+    fn move_kinds() {
+        let m = |uci| Move::from_uci(uci).unwrap();
+
+        let king = Piece::new(PieceType::King, PieceColor::WHITE);
+        assert_eq!(king.move_kind(m("e1f1"), false), MoveKind::Normal);
+        assert_eq!(king.move_kind(m("e1g1"), false), MoveKind::Castle);
+        assert_eq!(king.move_kind(m("e1e3"), false), MoveKind::Illegal);
+
+        let mut moved_king = Piece::new(PieceType::King, PieceColor::WHITE);
+        moved_king.move_piece();
+        assert_eq!(moved_king.move_kind(m("e1g1"), false), MoveKind::Illegal);
+
+        assert_eq!(Piece::new(PieceType::Rook, PieceColor::WHITE).move_kind(m("a1a8"), false), MoveKind::Sliding);
+        assert_eq!(Piece::new(PieceType::Knight, PieceColor::WHITE).move_kind(m("g1f3"), true), MoveKind::Normal);
+
+        let pawn = Piece::new(PieceType::Pawn, PieceColor::BLACK);
+        assert_eq!(pawn.move_kind(m("e7e5"), false), MoveKind::PawnDouble);
+        assert_eq!(pawn.move_kind(m("e7e5"), true), MoveKind::Illegal);
+        assert_eq!(pawn.move_kind(m("e2d1"), true), MoveKind::Promotion);
+        assert_eq!(pawn.move_kind(m("e2d1"), false), MoveKind::Illegal);
+
+        // compatibility with tuple form
+        assert_eq!(pawn.can_move_to(m("e7e5"), false), (true, true));
+        assert_eq!(king.can_move_to(m("e1g1"), false), (false, false));
+    }
+
+    #[test]
+    fn all_variants() {
+        assert_eq!(PieceType::ALL.len(), 6);
+        assert_eq!(PieceType::ALL, [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen, PieceType::King]);
+
+        assert_eq!(PieceColor::ALL.len(), 2);
+        assert_eq!(PieceColor::ALL, [PieceColor::WHITE, PieceColor::BLACK]);
+    }
@@ -0,0 +1,42 @@
+use chess_api::board::*;
+use chess_api::movement::*;
+use chess_api::piece::*;
+use chess_api::uci::*;
+
+#[test]
+    fn parse_position_with_moves() {
+        let board = parse_position("position startpos moves e2e4 e7e5 g1f3").unwrap();
+
+        assert_eq!(board.turn(), PieceColor::BLACK);
+        assert_eq!(board.last_move(), Some(Move::from_uci("g1f3").unwrap()));
+        assert_eq!(board.get_piece(Square::from_uci("f3").unwrap()).unwrap().piece_type(), PieceType::Knight);
+        assert!(board.get_piece(Square::from_uci("e2").unwrap()).is_none());
+
+        let board = parse_position("position fen 4k3/8/8/8/8/8/4P3/4K3 w - - 0 1 moves e2e4 e8d7").unwrap();
+
+        assert_eq!(board.to_fen(), "8/3k4/8/8/4P3/8/8/4K3 w - - 1 2");
+    }
+
+    #[test]
+    fn parse_position_errors() {
+        assert_eq!(parse_position("position").err(), Some(UciError::InvalidCommand("position".to_string())));
+        assert_eq!(parse_position("go startpos").err(), Some(UciError::InvalidCommand("go startpos".to_string())));
+        assert_eq!(parse_position("position fen 8/8 w - -").err(), Some(UciError::InvalidFen(FenError::WrongRankCount(2))));
+        assert_eq!(parse_position("position startpos moves e2e5").err(), Some(UciError::InvalidMove("e2e5".to_string())));
+        assert_eq!(parse_position("position startpos moves e2").err(), Some(UciError::InvalidMove("e2".to_string())));
+    }
+
+    #[test]
+    fn parse_go_limits() {
+        let go = parse_go("go movetime 1500 winc 10 binc 20 infinite").unwrap();
+
+        assert_eq!(go, GoCommand { movetime: Some(1500), winc: Some(10), binc: Some(20), infinite: true, ..GoCommand::default() });
+        assert_eq!(parse_go("go").unwrap(), GoCommand::default());
+        assert!(parse_go("go depth x").is_err());
+    }
+
+    #[test]
+    fn bestmove() {
+        assert_eq!(format_bestmove(Move::from_uci("e2e4").ok()), "bestmove e2e4");
+        assert_eq!(format_bestmove(Board::new().best_move(1)).len(), "bestmove e2e4".len());
+    }
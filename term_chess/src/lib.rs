@@ -1,5 +1,5 @@
 use std::io;
-use std::io::{ stdin, stdout, Read, Write };
+use std::io::Write;
 
 pub mod menu {
     pub mod main_menu;
@@ -8,6 +8,8 @@ pub mod menu {
     mod computer_menu;
 }
 
+pub mod play;
+
 pub use crate::menu::main_menu;
 
 //Utils for now here, will be moved somewhere else later (maybe)
@@ -36,6 +38,16 @@ impl Input {
     }
 
 
+    /// # Reads one line, None when standard input is closed
+    fn line() -> Option<String> {
+        let mut line = String::new();
+
+        match std::io::stdin().read_line(&mut line).expect("Error: Failed to take standard input!") {
+            0 => None,
+            _ => Some(line)
+        }
+    }
+
     fn proceed() -> bool {
         let mut line = String::new();
 
@@ -13,18 +13,28 @@ impl Menu for MainMenu {
             clean_screen();
             self.print();
 
-            self.choice = Input::one_to_(4);
+            self.choice = Input::one_to_(5);
         }
 
         match self.choice {
             1 => { Some(Box::new(QuickPairingMenu::new())) },
             2 => { Some(Box::new(FriendMenu::new())) },
             3 => { Some(Box::new(ComputerMenu::new())) },
+            4 => {
+                crate::play::run_game();
+                Some(Box::new(MainMenu::new()))
+            },
             _ => None
         }
     }
 }
 
+impl Default for MainMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MainMenu {
     pub fn new() -> MainMenu {
         MainMenu {
@@ -37,13 +47,7 @@ impl MainMenu {
         println!("  1. Quick pairing");
         println!("  2. Play with a friend");
         println!("  3. Play with the computer");
-        println!("  4. Exit");
+        println!("  4. Play on this terminal");
+        println!("  5. Exit");
     }
 }
-
-
-
-#[cfg(test)]
-mod test {
-    use super::*;
-}
@@ -0,0 +1,150 @@
+use std::io;
+use std::io::Write;
+
+use chess_api::board::{Board, Outcome, SanError};
+use chess_api::game::Game;
+use chess_api::movement::Move;
+use chess_api::piece::PieceColor;
+
+use crate::*;
+
+/// # Command entered by the player
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Move(Move), Quit
+}
+
+/// # Reasons why entered text is not a command
+#[derive(Debug, PartialEq)]
+pub enum InputError {
+    Empty, IllegalMove(String), Unrecognized(String)
+}
+
+/// # Parses player's input into command
+///
+/// moves are accepted in UCI or SAN and have to be legal for the side to move,
+/// `quit` or `q` ends the game
+pub fn parse_input(board: &Board, input: &str) -> Result<Command, InputError> {
+    let input = input.trim();
+
+    match input {
+        "" => return Err(InputError::Empty),
+        "quit" | "q" => return Ok(Command::Quit),
+        _ => ()
+    }
+
+    if let Ok(m) = Move::from_uci(input) {
+        return if board.is_legal(m) {
+            Ok(Command::Move(m))
+        } else { Err(InputError::IllegalMove(input.to_string())) };
+    }
+
+    match board.san_to_move(input) {
+        Ok(m) => Ok(Command::Move(m)),
+        Err(SanError::InvalidFormat) => Err(InputError::Unrecognized(input.to_string())),
+        Err(_) => Err(InputError::IllegalMove(input.to_string()))
+    }
+}
+
+/// # Plays game between two players sharing the terminal
+///
+/// loops until the game ends or a player quits
+pub fn run_game() {
+    let mut game = Game::new();
+    let mut message = String::new();
+
+    loop {
+        clean_screen();
+        println!("{}", game.board().render_colored());
+
+        if let Some(outcome) = game.board().outcome() {
+            announce(outcome);
+            break;
+        }
+
+        if !message.is_empty() {
+            println!("{}", message);
+            message.clear();
+        }
+
+        let side = if game.board().turn() == PieceColor::WHITE { "White" } else { "Black" };
+
+        if game.board().is_in_check(game.board().turn()) {
+            println!("{} is in check!", side);
+        }
+
+        print!("{} to move (UCI or SAN, q to quit): ", side);
+        io::stdout().flush().unwrap();
+
+        // closed input can't give any more moves
+        let command = match Input::line() {
+            Some(line) => parse_input(game.board(), &line),
+            None => Ok(Command::Quit)
+        };
+
+        match command {
+            Ok(Command::Move(m)) => {
+                game.make_move(m).expect("parsed move should be legal");
+            },
+            Ok(Command::Quit) => {
+                println!("\nGame abandoned");
+                break;
+            },
+            Err(InputError::Empty) => (),
+            Err(InputError::IllegalMove(input)) => message = format!("Illegal move: {}", input),
+            Err(InputError::Unrecognized(input)) => message = format!("Not a move: {}", input)
+        }
+    }
+
+    println!("\n{}", game.to_pgn());
+    print!("Press enter to continue...");
+    io::stdout().flush().unwrap();
+    Input::line();
+}
+
+fn announce(outcome: Outcome) {
+    let text = match outcome {
+        Outcome::Checkmate(PieceColor::WHITE) => "Checkmate, white wins",
+        Outcome::Checkmate(PieceColor::BLACK) => "Checkmate, black wins",
+        Outcome::Stalemate => "Stalemate, draw",
+        Outcome::DrawByRepetition => "Threefold repetition, draw",
+        Outcome::DrawByFiftyMove => "Fifty move rule, draw",
+        Outcome::DrawByInsufficientMaterial => "Insufficient material, draw"
+    };
+
+    println!("\n{}", text);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_input_moves() {
+        let board = Board::new();
+        let e4 = Command::Move(Move::from_uci("e2e4").unwrap());
+
+        assert_eq!(parse_input(&board, "e2e4"), Ok(e4));
+        assert_eq!(parse_input(&board, " Nf3\n"), Ok(Command::Move(Move::from_uci("g1f3").unwrap())));
+        assert_eq!(parse_input(&board, "e4"), Ok(Command::Move(Move::from_uci("e2e4").unwrap())));
+    }
+
+    #[test]
+    fn parse_input_errors() {
+        let board = Board::new();
+
+        assert_eq!(parse_input(&board, "\n"), Err(InputError::Empty));
+        assert_eq!(parse_input(&board, "e2e5"), Err(InputError::IllegalMove("e2e5".to_string())));
+        assert_eq!(parse_input(&board, "e7e5"), Err(InputError::IllegalMove("e7e5".to_string())));
+        assert_eq!(parse_input(&board, "Nf6"), Err(InputError::IllegalMove("Nf6".to_string())));
+        assert_eq!(parse_input(&board, "hello"), Err(InputError::Unrecognized("hello".to_string())));
+    }
+
+    #[test]
+    fn parse_input_quit() {
+        let board = Board::new();
+
+        assert_eq!(parse_input(&board, "quit"), Ok(Command::Quit));
+        assert_eq!(parse_input(&board, "q"), Ok(Command::Quit));
+    }
+}